@@ -0,0 +1,156 @@
+//! Collecting summarizable content of different types into a single feed.
+
+use std::path::Path;
+
+use crate::dates;
+use crate::registry::{Registry, RegistryError};
+use crate::Summary;
+
+/// A `Feed` holds items of any type that implements `Summary`, such as articles and tweets.
+///
+/// The items are stored as trait objects (`Box<dyn Summary>`), so one `Vec` can hold values of
+/// different concrete types, as long as they all implement the trait.
+#[derive(Default)]
+pub struct Feed {
+    items: Vec<Box<dyn Summary>>,
+}
+
+impl Feed {
+    /// Creates an empty feed.
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Adds an item to the end of the feed.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Any value implementing `Summary`; it is boxed and owned by the feed.
+    pub fn push(&mut self, item: impl Summary + 'static) {
+        self.items.push(Box::new(item));
+    }
+
+    /// Adds an item that is already boxed, such as one built at runtime.
+    pub fn push_boxed(&mut self, item: Box<dyn Summary>) {
+        self.items.push(item);
+    }
+
+    /// Reads a feed from a JSON array of items tagged with their type, like
+    /// `[{"type": "tweet", ...}, {"type": "podcast", ...}]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The JSON file.
+    /// * `registry` - The constructors for the types the file may contain.
+    ///
+    /// # Errors
+    ///
+    /// * `RegistryError` - If the file can't be read, or an item can't be built. See
+    ///   `Registry::parse`.
+    pub fn load_json(path: impl AsRef<Path>, registry: &Registry) -> Result<Self, RegistryError> {
+        Ok(registry.load(path)?.into_iter().collect())
+    }
+
+    /// Returns the number of items in the feed.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether the feed has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns an iterator over the items, in their current order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Summary> {
+        self.items.iter().map(|item| item.as_ref())
+    }
+
+    /// Sorts the feed newest first.
+    ///
+    /// Items without a publication time go last. The sort is stable, so items published at the
+    /// same time keep the order they were added in.
+    pub fn sort_by_time(&mut self) {
+        // `None` is less than any `Some`, so sorting in reverse puts the undated items last
+        self.items
+            .sort_by_key(|item| std::cmp::Reverse(item.published_at()));
+    }
+
+    /// Returns the items written by `author`.
+    ///
+    /// The comparison ignores case and a leading `@`, so both "horse_ebooks" and
+    /// "@Horse_Ebooks" match the tweets of `@horse_ebooks`.
+    ///
+    /// # Arguments
+    ///
+    /// * `author` - The author to look for, as returned by `summarize_author`.
+    pub fn by_author<'a>(&'a self, author: &str) -> impl Iterator<Item = &'a dyn Summary> {
+        let author = normalize_author(author);
+        self.iter()
+            .filter(move |item| normalize_author(&item.summarize_author()) == author)
+    }
+
+    /// Renders the feed as a digest, one line per item, in the feed's current order.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of items to include; the rest are counted at the end.
+    ///
+    /// # Returns
+    ///
+    /// * A multi-line string with a heading and the date, author and summary of each item.
+    pub fn digest(&self, limit: usize) -> String {
+        let mut digest = format!("Digest: {} item(s)\n", self.len());
+
+        for item in self.iter().take(limit) {
+            let date = item
+                .published_at()
+                .map(format_timestamp)
+                .unwrap_or_else(|| String::from("undated"));
+            digest.push_str(&format!(
+                "- [{date}] {}: {}\n",
+                item.summarize_author(),
+                item.summarize()
+            ));
+        }
+
+        if self.len() > limit {
+            digest.push_str(&format!("...and {} more\n", self.len() - limit));
+        }
+        digest
+    }
+}
+
+impl FromIterator<Box<dyn Summary>> for Feed {
+    fn from_iter<I: IntoIterator<Item = Box<dyn Summary>>>(iter: I) -> Self {
+        Self {
+            items: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<Box<dyn Summary>> for Feed {
+    fn extend<I: IntoIterator<Item = Box<dyn Summary>>>(&mut self, iter: I) {
+        self.items.extend(iter);
+    }
+}
+
+impl IntoIterator for Feed {
+    type Item = Box<dyn Summary>;
+    type IntoIter = std::vec::IntoIter<Box<dyn Summary>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+fn normalize_author(author: &str) -> String {
+    author.trim().trim_start_matches('@').to_lowercase()
+}
+
+/// Formats seconds since the Unix epoch as a UTC date and time, like "2024-08-05 14:30".
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (year, month, day) = dates::civil_from_days(timestamp / 86_400);
+    let minutes = timestamp % 86_400 / 60;
+    format!("{year:04}-{month:02}-{day:02} {:02}:{:02}", minutes / 60, minutes % 60)
+}
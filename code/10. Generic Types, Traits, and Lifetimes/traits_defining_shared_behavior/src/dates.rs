@@ -0,0 +1,157 @@
+//! Calendar arithmetic for timestamps in seconds since the Unix epoch, in UTC.
+//!
+//! The conversions are Howard Hinnant's `civil_from_days` and `days_from_civil`, restricted to
+//! dates from 1970 on.
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+
+/// Converts days since the Unix epoch to a (year, month, day) date in the Gregorian calendar.
+pub(crate) fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Shift the epoch to 0000-03-01, so leap days fall at the end of each year
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Converts a Gregorian date to days since the Unix epoch, or `None` before 1970, if the month
+/// or day is out of range or if the year is too large to count the days.
+pub(crate) fn days_from_civil(year: u64, month: u64, day: u64) -> Option<u64> {
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || year < 1970 {
+        return None;
+    }
+
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era.checked_mul(146_097)?
+        .checked_add(day_of_era)?
+        .checked_sub(719_468)
+}
+
+/// Formats a timestamp the way RSS wants it (RFC 822), like "Mon, 05 Aug 2024 16:00:00 +0000".
+pub(crate) fn to_rfc822(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let (year, month, day) = civil_from_days(days);
+    let seconds = timestamp % 86_400;
+
+    format!(
+        "{}, {day:02} {} {year} {:02}:{:02}:{:02} +0000",
+        WEEKDAYS[(days % 7) as usize],
+        MONTHS[(month - 1) as usize],
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
+
+/// Formats a timestamp the way Atom wants it (RFC 3339), like "2024-08-05T16:00:00Z".
+pub(crate) fn to_rfc3339(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days(timestamp / 86_400);
+    let seconds = timestamp % 86_400;
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
+
+/// Parses an RFC 822 date. The weekday is optional, seconds may be left out and the zone may be
+/// an offset like "-0400" or one of "GMT", "UT" and "Z".
+pub(crate) fn parse_rfc822(text: &str) -> Option<u64> {
+    let text = text.trim();
+    // Skip the weekday
+    let text = text.split_once(',').map_or(text, |(_, rest)| rest);
+
+    let mut parts = text.split_whitespace();
+    let day = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|name| name.eq_ignore_ascii_case(month))?;
+    let year = parts.next()?.parse().ok()?;
+    let time = parse_time(parts.next()?)?;
+    let offset = match parts.next() {
+        None | Some("GMT" | "UT" | "UTC" | "Z") => 0,
+        Some(zone) => parse_offset(zone)?,
+    };
+
+    let days = days_from_civil(year, month as u64 + 1, day)?;
+    apply_offset(seconds_from_days(days, time)?, offset)
+}
+
+/// Parses an RFC 3339 date, like "2024-08-05T16:00:00Z" or "2024-08-05T12:00:00.5-04:00".
+pub(crate) fn parse_rfc3339(text: &str) -> Option<u64> {
+    let (date, time) = text.trim().split_once(['T', 't', ' '])?;
+
+    let mut date = date.splitn(3, '-');
+    let year = date.next()?.parse().ok()?;
+    let month = date.next()?.parse().ok()?;
+    let day = date.next()?.parse().ok()?;
+
+    let (time, offset) = if let Some(time) = time.strip_suffix(['Z', 'z']) {
+        (time, 0)
+    } else {
+        let sign = time.rfind(['+', '-'])?;
+        (&time[..sign], parse_offset(&time[sign..].replace(':', ""))?)
+    };
+    // Fractions of a second are dropped
+    let time = time.split('.').next()?;
+
+    let days = days_from_civil(year, month, day)?;
+    apply_offset(seconds_from_days(days, parse_time(time)?)?, offset)
+}
+
+/// Adds `time` seconds to the start of day `days`, or `None` if that doesn't fit in a timestamp.
+fn seconds_from_days(days: u64, time: u64) -> Option<u64> {
+    days.checked_mul(86_400)?.checked_add(time)
+}
+
+/// Parses "HH:MM" or "HH:MM:SS" into seconds since midnight.
+fn parse_time(text: &str) -> Option<u64> {
+    let mut parts = text.split(':');
+    let hours: u64 = parts.next()?.parse().ok()?;
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let seconds: u64 = parts.next().map_or(Some(0), |seconds| seconds.parse().ok())?;
+
+    if parts.next().is_some() || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+    Some(hours * 3_600 + minutes * 60 + seconds)
+}
+
+/// Parses a zone offset like "+0200" or "-0430" into seconds east of UTC.
+fn parse_offset(text: &str) -> Option<i64> {
+    let (sign, digits) = match text.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let hours: i64 = digits[..2].parse().ok()?;
+    let minutes: i64 = digits[2..].parse().ok()?;
+    Some(sign * (hours * 3_600 + minutes * 60))
+}
+
+/// Turns a local time with an offset east of UTC into a UTC timestamp.
+fn apply_offset(local: u64, offset: i64) -> Option<u64> {
+    u64::try_from(i64::try_from(local).ok()?.checked_sub(offset)?).ok()
+}
@@ -1,5 +1,17 @@
+pub mod aggregator;
+mod dates;
+/// JSON for content files and notifications, from the chapter 7 workspace.
+pub use ::json;
+pub mod notifications;
+pub mod pair;
+pub mod registry;
+pub mod summarizer;
+pub mod syndication;
+pub mod templates;
+pub mod threads;
+
 /// The `Summary` trait defines a shared behavior for summarizing the content of a type.
-/// Any type that implements this trait must define the `summarize` method.
+/// Any type that implements this trait must define the `summarize_author` method.
 pub trait Summary {
     // V1
     /*
@@ -34,22 +46,99 @@ pub trait Summary {
 
     /// Returns a summary of the content, including the author.
     ///
-    /// This method has a default implementation that returns a summary string including the author.
+    /// This method has a default implementation that renders the English "item" template, so it
+    /// returns a summary string including the author.
     /// Types implementing this trait can override this method to provide a custom summary.
     /// The compiler ensures that any type with the `Summary` trait has the `summarize` method
     /// defined with this exact signature.
     fn summarize(&self) -> String {
-        format!("(Read more from {}...)", self.summarize_author())
+        templates::Template::parse(templates::ITEM_TEMPLATE)
+            .and_then(|template| template.render(self, templates::Format::PlainText))
+            .expect("the item template only needs the author, which every item has")
+    }
+
+    /// Returns a summary that is at most `max_chars` characters long.
+    ///
+    /// The default implementation cuts `summarize` at a word boundary with `truncate_at_word`.
+    /// Types with long content can override it to decide which parts to keep.
+    fn summarize_within(&self, max_chars: usize) -> String {
+        truncate_at_word(&self.summarize(), max_chars)
+    }
+
+    /// Returns when the content was published, in seconds since the Unix epoch.
+    ///
+    /// The default implementation returns `None` for content that doesn't know its publication
+    /// time. A feed lists such items after the dated ones.
+    fn published_at(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the name of the type of content, like "article" or "tweet".
+    ///
+    /// Notification sinks use it to pick the items they want. The default implementation returns
+    /// "item" for types that don't say.
+    fn content_type(&self) -> &str {
+        "item"
+    }
+
+    /// Returns the value of a named field, like "headline", for summary templates.
+    ///
+    /// The default implementation has no fields; templates still know "author", "type", "summary"
+    /// and "date" from the other methods.
+    fn field(&self, _name: &str) -> Option<String> {
+        None
     }
 }
 
+/// Shortens `text` to at most `max_chars` characters without cutting a word in half.
+///
+/// Text that already fits is returned unchanged. Otherwise as many whole words as fit are kept,
+/// followed by "...", which counts toward the limit. A single word longer than the limit is cut.
+///
+/// # Arguments
+///
+/// * `text` - The text to shorten.
+/// * `max_chars` - The maximum length of the result, in characters.
+pub fn truncate_at_word(text: &str, max_chars: usize) -> String {
+    const ELLIPSIS: &str = "...";
+
+    let text = text.trim();
+    if text.chars().count() <= max_chars {
+        return String::from(text);
+    }
+    if max_chars <= ELLIPSIS.len() {
+        return ELLIPSIS.chars().take(max_chars).collect();
+    }
+
+    let budget = max_chars - ELLIPSIS.len();
+    // The byte offset just past the last character that fits
+    let end = text
+        .char_indices()
+        .nth(budget)
+        .map_or(text.len(), |(index, _)| index);
+
+    // Cut where a word ends: before the whitespace at `end`, or before the last space that fits
+    let kept = if text[end..].starts_with(char::is_whitespace) {
+        &text[..end]
+    } else {
+        match text[..end].rfind(char::is_whitespace) {
+            Some(space) => &text[..space],
+            None => &text[..end],
+        }
+    };
+
+    format!("{}{ELLIPSIS}", kept.trim_end())
+}
 
 /// A `NewsArticle` struct representing a news article with a headline, location, author, and content.
+#[derive(Debug, Clone, PartialEq)]
 pub struct NewsArticle {
     pub headline: String,
     pub location: String,
     pub author: String,
     pub content: String,
+    /// When the article was published, in seconds since the Unix epoch.
+    pub published_at: u64,
 }
 
 // V1
@@ -65,6 +154,7 @@ impl Summary for NewsArticle {
 */
 
 // V2
+/*
 /// Implementing the `Summary` trait for `NewsArticle` with default implementation.
 ///
 /// To use a default implementation to summarize instances of `NewsArticle`.
@@ -72,15 +162,99 @@ impl Summary for NewsArticle {
 /// Even though we're no longer defining the summarize method on `NewsArticle` directly,
 /// we've provided a default implementation and specified that `NewsArticle` implements
 /// the `Summary` trait. As a result, we can still call the summarize method on an instance of `NewsArticle`.
-// impl Summary for NewsArticle {}
-// BROKEN DUE TO THE summarize_author IMPLEMENTATION
+impl Summary for NewsArticle {}
+*/
+
+impl NewsArticle {
+    /// How long `summarize` lets an article's summary get, in characters.
+    pub const SUMMARY_LENGTH: usize = 140;
+
+    /// How many sentences of the content a summary quotes at most.
+    pub const SUMMARY_SENTENCES: usize = 3;
+}
+
+// V3
+/// Implementing the `Summary` trait for `NewsArticle`.
+///
+/// Since `Summary` gained `summarize_author`, which has no default implementation, the empty impl
+/// block of V2 no longer compiles. The article's author fills it in. The summary starts like the
+/// one of V1 and continues with as much of the content as the length allows.
+impl Summary for NewsArticle {
+    fn summarize_author(&self) -> String {
+        self.author.clone()
+    }
+
+    fn summarize(&self) -> String {
+        self.summarize_within(Self::SUMMARY_LENGTH)
+    }
+
+    /// Keeps the headline, author and location, and fills the rest of `max_chars` with the most
+    /// telling sentences of the content, as picked by `summarizer::extract`. When no sentence
+    /// fits, the start of the content is used instead, cut at a word boundary. If even the
+    /// headline doesn't fit, it is cut instead.
+    fn summarize_within(&self, max_chars: usize) -> String {
+        let heading = format!(
+            "{}, by {} ({})",
+            self.headline,
+            self.summarize_author(),
+            self.location
+        );
+        let separator = ": ";
+        let used = heading.chars().count() + separator.len();
+
+        // Without room for at least a word of content, leave it out
+        let content = match max_chars.checked_sub(used) {
+            Some(remaining) if remaining > 3 => {
+                let sentences =
+                    summarizer::extract(&self.content, Self::SUMMARY_SENTENCES, remaining);
+                if sentences.is_empty() {
+                    truncate_at_word(&self.content, remaining)
+                } else {
+                    sentences
+                }
+            }
+            _ => String::new(),
+        };
+
+        if content.is_empty() {
+            truncate_at_word(&heading, max_chars)
+        } else {
+            format!("{heading}{separator}{content}")
+        }
+    }
+
+    fn published_at(&self) -> Option<u64> {
+        Some(self.published_at)
+    }
+
+    fn content_type(&self) -> &str {
+        "article"
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        match name {
+            "headline" => Some(self.headline.clone()),
+            "location" => Some(self.location.clone()),
+            "content" => Some(self.content.clone()),
+            _ => None,
+        }
+    }
+}
 
 /// A `Tweet` struct representing a tweet with a username, content, and metadata about replies and retweets.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Tweet {
+    pub id: u64,
     pub username: String,
     pub content: String,
     pub reply: bool,
     pub retweet: bool,
+    /// The tweet this one replies to, if `reply`, or retweets, if `retweet`.
+    pub parent: Option<u64>,
+    /// A tweet quoted in this one.
+    pub quoted: Option<u64>,
+    /// When the tweet was posted, in seconds since the Unix epoch.
+    pub published_at: u64,
 }
 
 /// Implementing the `Summary` trait for `Tweet`.
@@ -98,4 +272,20 @@ impl Summary for Tweet {
     fn summarize_author(&self) -> String {
         format!("@{}", self.username)
     }
-}
\ No newline at end of file
+
+    fn published_at(&self) -> Option<u64> {
+        Some(self.published_at)
+    }
+
+    fn content_type(&self) -> &str {
+        "tweet"
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        match name {
+            "username" => Some(self.username.clone()),
+            "content" => Some(self.content.clone()),
+            _ => None,
+        }
+    }
+}
@@ -1,4 +1,12 @@
 // This is aggregator in the Rust book
+use traits_defining_shared_behavior::aggregator::Feed;
+use traits_defining_shared_behavior::notifications::{Dispatcher, RateLimit, StdoutSink};
+use traits_defining_shared_behavior::json::Json;
+use traits_defining_shared_behavior::pair::Pair;
+use traits_defining_shared_behavior::registry::Registry;
+use traits_defining_shared_behavior::syndication::{self, Channel, Entry};
+use traits_defining_shared_behavior::templates::{Format, Templates};
+use traits_defining_shared_behavior::threads::Timeline;
 use traits_defining_shared_behavior::{NewsArticle, Summary, Tweet};
 use std::fmt::Display;
 
@@ -9,7 +17,7 @@ use std::fmt::Display;
 ///
 /// * `item` - A reference to an item that implements the `Summary` trait.
 pub fn notify(item: &impl Summary) {
-    println!("Breaking news! {}", item.summarize());
+    breaking_news(item);
 }
 
 /// The `notify_with_trait_bound` function uses trait bound syntax to accept any type that implements
@@ -20,7 +28,7 @@ pub fn notify(item: &impl Summary) {
 ///
 /// * `item` - A reference to an item that implements the `Summary` trait.
 pub fn notify_with_trait_bound<T: Summary>(item: &T) {
-    println!("Breaking news! {}", item.summarize());
+    breaking_news(item);
 }
 
 /// The `notify_with_multiple_bounds` function accepts any type that implements both `Summary` and
@@ -31,7 +39,7 @@ pub fn notify_with_trait_bound<T: Summary>(item: &T) {
 ///
 /// * `item` - A reference to an item that implements both the `Summary` and `Display` traits.
 pub fn notify_with_multiple_bounds(item: &(impl Summary + Display)) {
-    println!("Breaking news! {}", item.summarize());
+    breaking_news(item);
     println!("Formatted item: {}", item);
 }
 
@@ -43,10 +51,24 @@ pub fn notify_with_multiple_bounds(item: &(impl Summary + Display)) {
 ///
 /// * `item` - A reference to an item that implements both the `Summary` and `Display` traits.
 pub fn notify_with_multiple_trait_bounds<T: Summary + Display>(item: &T) {
-    println!("Breaking news! {}", item.summarize());
+    breaking_news(item);
     println!("Formatted item: {}", item);
 }
 
+/// Sends `item` through a `Dispatcher` whose only sink is `StdoutSink`, which prints
+/// "Breaking news!" and the summary.
+///
+/// # Arguments
+///
+/// * `item` - A reference to an item that implements the `Summary` trait.
+fn breaking_news(item: &impl Summary) {
+    let mut dispatcher = Dispatcher::new();
+    dispatcher.subscribe(StdoutSink);
+    for (sink, error) in dispatcher.notify(item).failed {
+        eprintln!("Couldn't notify {sink}: {error}");
+    }
+}
+
 /// The `some_function` uses a where clause to simplify the function signature when
 /// multiple trait bounds are needed.
 ///
@@ -63,7 +85,7 @@ where
     T: Display + Clone,
     U: Clone + std::fmt::Debug,
 {
-    println!("t: {}, u: {}", t, u);
+    println!("t: {}, u: {:?}", t, u);
     42
 }
 
@@ -75,63 +97,57 @@ where
 /// * A `Tweet` instance that implements the `Summary` trait.
 pub fn returns_summarizable() -> impl Summary {
     Tweet {
+        id: 1,
         username: String::from("horse_ebooks"),
         content: String::from(
             "of course, as you probably already know, people",
         ),
         reply: false,
         retweet: false,
+        parent: None,
+        quoted: None,
+        published_at: 1_722_866_400,
     }
 }
 
-/// The `Pair` struct is a generic type that holds two values of Type `T`.
-struct Pair<T> {
-    x: T,
-    y: T,
+/// A `BlogPost` is a content type the library doesn't know, registered at runtime.
+struct BlogPost {
+    blog: String,
+    title: String,
 }
 
-impl<T> Pair<T> {
-    /// Creates a new instance of `Pair`.
-    ///
-    /// # Arguments
-    ///
-    /// * `x` - The first value of the pair.
-    /// * `y` - The second value of the pair.
-    ///
-    /// # Returns
-    ///
-    /// * A new instance of `Pair`.
-    fn new(x: T, y: T) -> Self {
-        Self { x, y }
+impl Summary for BlogPost {
+    fn summarize_author(&self) -> String {
+        self.blog.clone()
     }
-}
 
-impl<T: Display + PartialOrd> Pair<T> {
-    /// Compares and displays the larger member of the pair.
-    ///
-    /// This method is only available if `T` implements both `Display` and `PartialOrd`.
-    fn cmp_display(&self) {
-        if self.x >= self.y {
-            println!("The largest member is x = {}", self.x);
-        } else {
-            println!("The largest member is y = {}", self.y);
-        }
+    fn summarize(&self) -> String {
+        format!("New on {}: {}", self.blog, self.title)
+    }
+
+    fn content_type(&self) -> &str {
+        "blog_post"
     }
 }
 
 fn use_pair_example() {
     let pair = Pair::new(5, 10);
     pair.cmp_display();
+    println!("{pair} sorted is {}, and its smaller member is {}", pair.sorted(), pair.min());
 }
 
 fn main() {
     let tweet = Tweet{
+        id: 2,
         username: String::from("horse_ebooks"),
         content: String::from(
             "of course, as you probably already know, people",
         ),
         reply: false,
         retweet: false,
+        parent: None,
+        quoted: None,
+        published_at: 1_722_870_000,
     };
 
     // Display the summary of the tweet
@@ -145,6 +161,7 @@ fn main() {
             "The Pittsburgh Penguins once again are the best \
             hockey team in the NHL.",
         ),
+        published_at: 1_722_873_600,
     };
 
     // Use the notify functions to print the summary of the tweet
@@ -158,6 +175,79 @@ fn main() {
     // Call the function that uses Pair with conditional method implementation
     use_pair_example();
 
-    // println!("New article available! {}", article.summarize());
-    // BROKEN IN LIB
+    println!("New article available! {}", article.summarize());
+    println!("In short: {}", article.summarize_within(100));
+
+    // Summaries can also come from templates, per content type and locale
+    let templates = Templates::default();
+    for (locale, format) in [("en", Format::Markdown), ("fr", Format::Html)] {
+        match templates.render(&article, locale, format) {
+            Ok(summary) => println!("[{locale}] {summary}"),
+            Err(error) => eprintln!("Couldn't render the summary: {error}"),
+        }
+    }
+
+    // Export as RSS and read it back: parsed entries implement Summary, so notify takes them
+    let channel = Channel {
+        title: String::from("Breaking news"),
+        link: String::from("https://example.com/"),
+        description: String::from("Articles and tweets"),
+    };
+    let entries = [Entry::from(article.clone()), Entry::from(tweet.clone())];
+    let rss = syndication::to_rss(&channel, &entries);
+    match syndication::parse(&rss) {
+        Ok(entries) => entries.iter().for_each(notify),
+        Err(error) => eprintln!("Couldn't read the feed back: {error}"),
+    }
+
+    // A dispatcher sends news to several sinks; this one only wants tweets, and not too many
+    let mut dispatcher = Dispatcher::new();
+    dispatcher.subscribe(StdoutSink).only(["tweet"]).rate_limit(RateLimit {
+        repeats: 1,
+        window: 60,
+    });
+    dispatcher.notify(&article);
+    dispatcher.notify(&tweet);
+    dispatcher.notify(&tweet);
+
+    // Replies know their parent, so a thread can be put back together and summarized
+    let answer = Tweet {
+        id: 3,
+        username: String::from("ferris"),
+        content: String::from("people what?"),
+        reply: true,
+        retweet: false,
+        parent: Some(tweet.id),
+        quoted: None,
+        published_at: 1_722_871_000,
+    };
+    let timeline = Timeline::new([tweet.clone(), answer]);
+    if let Ok(thread) = timeline.thread(3) {
+        println!("Thread: {}", thread.summarize());
+    }
+
+    // Articles and tweets can share a feed, as trait objects
+    let mut feed = Feed::new();
+    feed.push(tweet);
+    feed.push(article);
+    feed.push(summarizable);
+
+    // Types registered at startup can be read from tagged JSON next to the built-in ones
+    let mut registry = Registry::new();
+    let registered = registry.register("blog_post", |item: &Json| {
+        Ok(BlogPost {
+            blog: String::from(item.field_str("blog")?),
+            title: String::from(item.field_str("title")?),
+        })
+    });
+    if let Err(error) = registered {
+        eprintln!("Couldn't register blog posts: {error}");
+    }
+    let json = r#"[{"type": "blog_post", "blog": "Inside Rust", "title": "Traits at runtime"}]"#;
+    match registry.parse(json) {
+        Ok(items) => feed.extend(items),
+        Err(error) => eprintln!("Couldn't read the items: {error}"),
+    }
+    feed.sort_by_time();
+    print!("{}", feed.digest(10));
 }
\ No newline at end of file
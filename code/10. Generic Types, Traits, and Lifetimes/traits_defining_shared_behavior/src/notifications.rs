@@ -0,0 +1,391 @@
+//! Sending "Breaking news!" to more places than the terminal.
+//!
+//! A `Dispatcher` turns each `Summary` item into a `Notification` and hands it to every sink that
+//! wants it. Sinks implement `NotificationSink`; each one can be limited to some content types
+//! and rate-limited, so a burst of repeated tweets doesn't flood it.
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::json::Json;
+use crate::Summary;
+
+/// What a sink is told about an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub content_type: String,
+    pub author: String,
+    pub summary: String,
+    pub published_at: Option<u64>,
+    /// The item's "content" field, if it has one. Summaries can be the same for different items,
+    /// so repeats are told apart by this.
+    pub content: Option<String>,
+}
+
+impl Notification {
+    /// Takes down what a notification needs to say about `item`.
+    pub fn about(item: &(impl Summary + ?Sized)) -> Self {
+        Self {
+            content_type: String::from(item.content_type()),
+            author: item.summarize_author(),
+            summary: item.summarize(),
+            published_at: item.published_at(),
+            content: item.field("content"),
+        }
+    }
+
+    /// What makes two notifications the same as far as `RateLimit` is concerned.
+    fn repeat_key(&self) -> (String, String) {
+        let content = self.content.as_ref().unwrap_or(&self.summary);
+        (self.author.clone(), content.clone())
+    }
+
+    /// The notification as a JSON object, as sent by `WebhookSink`.
+    pub fn to_json(&self) -> String {
+        let published_at = self.published_at.map_or(Json::Null, Json::from);
+        Json::object([
+            ("type", Json::from(self.content_type.as_str())),
+            ("author", Json::from(self.author.as_str())),
+            ("summary", Json::from(self.summary.as_str())),
+            ("published_at", published_at),
+        ])
+        .to_string()
+    }
+}
+
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Breaking news! {}", self.summary)
+    }
+}
+
+#[derive(Debug)]
+pub enum NotifyError {
+    Io(io::Error),
+    /// Webhooks may only be sent to this machine.
+    NotLocal(SocketAddr),
+    /// The webhook answered with something other than a 2xx status.
+    Rejected { status: String },
+}
+
+impl fmt::Display for NotifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NotifyError::Io(error) => write!(f, "couldn't deliver the notification: {error}"),
+            NotifyError::NotLocal(address) => {
+                write!(f, "{address} is not a local address; webhooks stay on this machine")
+            }
+            NotifyError::Rejected { status } => write!(f, "the webhook answered '{status}'"),
+        }
+    }
+}
+
+impl Error for NotifyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NotifyError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for NotifyError {
+    fn from(error: io::Error) -> Self {
+        NotifyError::Io(error)
+    }
+}
+
+/// Somewhere notifications can be delivered.
+pub trait NotificationSink {
+    /// Returns a name for the sink, used when reporting failures.
+    fn name(&self) -> String;
+
+    /// Delivers one notification.
+    fn send(&mut self, notification: &Notification) -> Result<(), NotifyError>;
+}
+
+/// Prints notifications to standard output, like `notify` does.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutSink;
+
+impl NotificationSink for StdoutSink {
+    fn name(&self) -> String {
+        String::from("stdout")
+    }
+
+    fn send(&mut self, notification: &Notification) -> Result<(), NotifyError> {
+        let mut stdout = io::stdout().lock();
+        writeln!(stdout, "{notification}")?;
+        Ok(())
+    }
+}
+
+/// Appends notifications to a file, one line each.
+#[derive(Debug)]
+pub struct FileSink {
+    path: PathBuf,
+    file: File,
+}
+
+impl FileSink {
+    /// Opens `path` for appending, creating it if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, NotifyError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { path, file })
+    }
+}
+
+impl NotificationSink for FileSink {
+    fn name(&self) -> String {
+        format!("file {}", self.path.display())
+    }
+
+    fn send(&mut self, notification: &Notification) -> Result<(), NotifyError> {
+        writeln!(self.file, "{notification}")?;
+        Ok(())
+    }
+}
+
+/// POSTs each notification as JSON to an HTTP endpoint on this machine.
+#[derive(Debug, Clone)]
+pub struct WebhookSink {
+    address: SocketAddr,
+    path: String,
+}
+
+impl WebhookSink {
+    /// Creates a webhook for `path` on `address`.
+    ///
+    /// # Errors
+    ///
+    /// * `NotifyError::NotLocal` - If `address` isn't a loopback address.
+    pub fn new(address: SocketAddr, path: &str) -> Result<Self, NotifyError> {
+        if !address.ip().is_loopback() {
+            return Err(NotifyError::NotLocal(address));
+        }
+        Ok(Self {
+            address,
+            path: String::from(path),
+        })
+    }
+}
+
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> String {
+        format!("webhook http://{}{}", self.address, self.path)
+    }
+
+    fn send(&mut self, notification: &Notification) -> Result<(), NotifyError> {
+        let mut stream = TcpStream::connect_timeout(&self.address, Duration::from_secs(2))?;
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+        let body = notification.to_json();
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.path,
+            self.address,
+            body.len()
+        )?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        let status = response.lines().next().unwrap_or_default();
+        match status.split_whitespace().nth(1) {
+            Some(code) if code.starts_with('2') => Ok(()),
+            _ => Err(NotifyError::Rejected {
+                status: String::from(status),
+            }),
+        }
+    }
+}
+
+/// Keeps notifications in memory, for tests.
+///
+/// Clones share the same list, so keep one to look at what a sink given to a dispatcher received.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+    received: Arc<Mutex<Vec<Notification>>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of every notification received so far, oldest first.
+    pub fn received(&self) -> Vec<Notification> {
+        self.received
+            .lock()
+            .map(|received| received.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl NotificationSink for MemorySink {
+    fn name(&self) -> String {
+        String::from("memory")
+    }
+
+    fn send(&mut self, notification: &Notification) -> Result<(), NotifyError> {
+        if let Ok(mut received) = self.received.lock() {
+            received.push(notification.clone());
+        }
+        Ok(())
+    }
+}
+
+/// How often a sink accepts the same notification, by the same author with the same content (or
+/// summary, for items without content), within a sliding window of time. Different items from one
+/// author aren't limited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub repeats: usize,
+    /// The length of the window, in seconds.
+    pub window: u64,
+}
+
+/// A sink with its filter and rate limit.
+pub struct Subscription {
+    sink: Box<dyn NotificationSink>,
+    content_types: Option<Vec<String>>,
+    rate_limit: Option<RateLimit>,
+    /// When recent notifications were delivered, by `Notification::repeat_key`, oldest first.
+    recent: HashMap<(String, String), VecDeque<u64>>,
+}
+
+impl Subscription {
+    /// Only sends items of the given content types to this sink.
+    ///
+    /// # Arguments
+    ///
+    /// * `content_types` - Names as returned by `Summary::content_type`, like "article".
+    pub fn only<const N: usize>(&mut self, content_types: [&str; N]) -> &mut Self {
+        self.content_types = Some(content_types.iter().map(|name| String::from(*name)).collect());
+        self
+    }
+
+    /// Limits how often this sink is notified.
+    pub fn rate_limit(&mut self, rate_limit: RateLimit) -> &mut Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    fn wants(&self, notification: &Notification) -> bool {
+        self.content_types
+            .as_ref()
+            .is_none_or(|types| types.contains(&notification.content_type))
+    }
+
+    /// Returns whether the rate limit lets `notification` through at `now`.
+    ///
+    /// Nothing is counted here: only `delivered` does that, so a failed send uses up no
+    /// allowance. Deliveries that have left the window are forgotten, for every item, so only
+    /// items delivered within the last window are remembered.
+    fn admits(&mut self, notification: &Notification, now: u64) -> bool {
+        let Some(limit) = self.rate_limit else {
+            return true;
+        };
+        self.recent.retain(|_, recent| {
+            while recent
+                .front()
+                .is_some_and(|sent| now.saturating_sub(*sent) >= limit.window)
+            {
+                recent.pop_front();
+            }
+            !recent.is_empty()
+        });
+
+        self.recent
+            .get(&notification.repeat_key())
+            .is_none_or(|recent| recent.len() < limit.repeats)
+    }
+
+    /// Counts `notification` against the rate limit, once it was sent at `now`.
+    fn delivered(&mut self, notification: &Notification, now: u64) {
+        if self.rate_limit.is_some() {
+            let recent = self.recent.entry(notification.repeat_key()).or_default();
+            recent.push_back(now);
+        }
+    }
+}
+
+/// What happened to one item sent through a dispatcher.
+#[derive(Debug, Default)]
+pub struct DispatchReport {
+    /// The number of sinks the notification reached.
+    pub delivered: usize,
+    /// The number of sinks that don't take this content type.
+    pub filtered: usize,
+    /// The number of sinks that had already heard this often about the same item.
+    pub rate_limited: usize,
+    /// The sinks that failed, by name, and why.
+    pub failed: Vec<(String, NotifyError)>,
+}
+
+/// Sends notifications to every subscribed sink.
+#[derive(Default)]
+pub struct Dispatcher {
+    subscriptions: Vec<Subscription>,
+}
+
+impl Dispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a sink that gets every notification, and returns its subscription to narrow that
+    /// down with `only` or `rate_limit`.
+    pub fn subscribe(&mut self, sink: impl NotificationSink + 'static) -> &mut Subscription {
+        self.subscriptions.push(Subscription {
+            sink: Box::new(sink),
+            content_types: None,
+            rate_limit: None,
+            recent: HashMap::new(),
+        });
+        self.subscriptions.last_mut().expect("a subscription was just added")
+    }
+
+    /// Notifies the sinks about `item`, now.
+    pub fn notify(&mut self, item: &(impl Summary + ?Sized)) -> DispatchReport {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        self.notify_at(item, now)
+    }
+
+    /// Notifies the sinks about `item` as if it were `now`, in seconds since the Unix epoch.
+    ///
+    /// A sink that fails doesn't stop the others from being notified.
+    pub fn notify_at(&mut self, item: &(impl Summary + ?Sized), now: u64) -> DispatchReport {
+        let notification = Notification::about(item);
+        let mut report = DispatchReport::default();
+
+        for subscription in &mut self.subscriptions {
+            if !subscription.wants(&notification) {
+                report.filtered += 1;
+            } else if !subscription.admits(&notification, now) {
+                report.rate_limited += 1;
+            } else {
+                match subscription.sink.send(&notification) {
+                    Ok(()) => {
+                        subscription.delivered(&notification, now);
+                        report.delivered += 1;
+                    }
+                    Err(error) => report.failed.push((subscription.sink.name(), error)),
+                }
+            }
+        }
+
+        report
+    }
+}
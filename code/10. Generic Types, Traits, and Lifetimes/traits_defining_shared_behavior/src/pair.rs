@@ -0,0 +1,215 @@
+//! Two values of the same type, and what can be done with them depending on what the type can do.
+//!
+//! Each `impl` block asks only for the traits its methods need, so a `Pair` of anything can be
+//! swapped and mapped, a `Pair` of comparable values can give its larger member, and so on.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+
+/// The `Pair` struct is a generic type that holds two values of Type `T`.
+///
+/// Pairs compare and hash like the tuple `(x, y)`, when `T` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Pair<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Pair<T> {
+    /// Creates a new instance of `Pair`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The first value of the pair.
+    /// * `y` - The second value of the pair.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Pair`.
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the pair with its members exchanged.
+    pub fn swap(self) -> Self {
+        Self {
+            x: self.y,
+            y: self.x,
+        }
+    }
+
+    /// Applies `f` to both members, `x` first.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Pair<U> {
+        Pair {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+
+    /// Borrows both members, to use the consuming methods without giving the pair up.
+    pub fn as_ref(&self) -> Pair<&T> {
+        Pair {
+            x: &self.x,
+            y: &self.y,
+        }
+    }
+
+    /// Returns the member `compare` finds larger, or `x` if they're equal.
+    pub fn max_by(self, compare: impl FnOnce(&T, &T) -> Ordering) -> T {
+        match compare(&self.x, &self.y) {
+            Ordering::Less => self.y,
+            Ordering::Equal | Ordering::Greater => self.x,
+        }
+    }
+
+    /// Returns the member `compare` finds smaller, or `x` if they're equal.
+    pub fn min_by(self, compare: impl FnOnce(&T, &T) -> Ordering) -> T {
+        match compare(&self.x, &self.y) {
+            Ordering::Greater => self.y,
+            Ordering::Equal | Ordering::Less => self.x,
+        }
+    }
+
+    /// Returns the member with the larger key, or `x` if the keys are equal.
+    pub fn max_by_key<K: Ord>(self, mut key: impl FnMut(&T) -> K) -> T {
+        self.max_by(|x, y| key(x).cmp(&key(y)))
+    }
+
+    /// Returns the member with the smaller key, or `x` if the keys are equal.
+    pub fn min_by_key<K: Ord>(self, mut key: impl FnMut(&T) -> K) -> T {
+        self.min_by(|x, y| key(x).cmp(&key(y)))
+    }
+
+    /// Returns the pair with the member with the smaller key first. Equal keys keep their order.
+    pub fn sort_by_key<K: Ord>(self, mut key: impl FnMut(&T) -> K) -> Self {
+        if key(&self.x) > key(&self.y) {
+            self.swap()
+        } else {
+            self
+        }
+    }
+}
+
+impl<T: PartialOrd> Pair<T> {
+    /// Returns the larger member, or `x` if they're equal or can't be compared.
+    ///
+    /// This method is only available if `T` implements `PartialOrd`.
+    pub fn max(self) -> T {
+        self.max_by(|x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal))
+    }
+
+    /// Returns the smaller member, or `x` if they're equal or can't be compared.
+    ///
+    /// This method is only available if `T` implements `PartialOrd`.
+    pub fn min(self) -> T {
+        self.min_by(|x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal))
+    }
+}
+
+impl<T: Display + PartialOrd> Pair<T> {
+    /// Compares and displays the larger member of the pair.
+    ///
+    /// This method is only available if `T` implements both `Display` and `PartialOrd`.
+    pub fn cmp_display(&self) {
+        if self.x >= self.y {
+            println!("The largest member is x = {}", self.x);
+        } else {
+            println!("The largest member is y = {}", self.y);
+        }
+    }
+}
+
+impl<T: Ord> Pair<T> {
+    /// Returns the pair in order, smaller member first.
+    ///
+    /// This method is only available if `T` implements `Ord`.
+    pub fn sorted(self) -> OrderedPair<T> {
+        OrderedPair::new(self.x, self.y)
+    }
+}
+
+/// Displays the pair like a tuple, as "(x, y)".
+impl<T: Display> Display for Pair<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Pair<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Pair<T>> for (T, T) {
+    fn from(pair: Pair<T>) -> Self {
+        (pair.x, pair.y)
+    }
+}
+
+/// A pair whose first member is never larger than its second, like the bounds of a range.
+///
+/// The members are private so the order can't be broken; `low` and `high` read them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderedPair<T> {
+    low: T,
+    high: T,
+}
+
+impl<T: Ord> OrderedPair<T> {
+    /// Creates a pair of `a` and `b`, in order.
+    pub fn new(a: T, b: T) -> Self {
+        if a <= b {
+            Self { low: a, high: b }
+        } else {
+            Self { low: b, high: a }
+        }
+    }
+
+    /// Whether `value` lies between the members, both included.
+    pub fn contains(&self, value: &T) -> bool {
+        self.low <= *value && *value <= self.high
+    }
+}
+
+impl<T> OrderedPair<T> {
+    /// The smaller member.
+    pub fn low(&self) -> &T {
+        &self.low
+    }
+
+    /// The larger member.
+    pub fn high(&self) -> &T {
+        &self.high
+    }
+
+    /// Returns the members as a plain pair, smaller one as `x`.
+    pub fn into_pair(self) -> Pair<T> {
+        Pair::new(self.low, self.high)
+    }
+}
+
+impl<T: Ord> From<Pair<T>> for OrderedPair<T> {
+    fn from(pair: Pair<T>) -> Self {
+        pair.sorted()
+    }
+}
+
+impl<T: Ord> From<(T, T)> for OrderedPair<T> {
+    fn from((a, b): (T, T)) -> Self {
+        Self::new(a, b)
+    }
+}
+
+impl<T> From<OrderedPair<T>> for Pair<T> {
+    fn from(pair: OrderedPair<T>) -> Self {
+        pair.into_pair()
+    }
+}
+
+/// Displays the pair like a closed range, as "[low, high]".
+impl<T: Display> Display for OrderedPair<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}, {}]", self.low, self.high)
+    }
+}
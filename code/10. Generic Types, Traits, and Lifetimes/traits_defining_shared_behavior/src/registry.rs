@@ -0,0 +1,217 @@
+//! Building summarizable items of types chosen at runtime.
+//!
+//! `impl Summary` in a return type still means one concrete type. A `Registry` maps content-type
+//! names to constructors that read an item from JSON and return it as a `Box<dyn Summary>`, so
+//! types like blog posts or podcasts can be added at startup without the crate knowing them.
+//! Items are tagged with their type: `{"type": "tweet", "username": "horse_ebooks", ...}`.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::json::Json;
+use crate::{NewsArticle, Summary, Tweet};
+
+/// Reads an item from its JSON object, or says what's wrong with it.
+pub type Constructor = Box<dyn Fn(&Json) -> Result<Box<dyn Summary>, String>>;
+
+#[derive(Debug)]
+pub enum RegistryError {
+    Io(io::Error),
+    /// The file isn't valid JSON.
+    Json(String),
+    /// The file isn't an array of items.
+    NotAList,
+    /// A type with this name is registered already.
+    AlreadyRegistered(String),
+    /// The item at `index` has no "type" string.
+    Untagged { index: usize },
+    /// No constructor is registered for the item's type.
+    UnknownType { index: usize, content_type: String },
+    /// The constructor refused the item.
+    Invalid {
+        index: usize,
+        content_type: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistryError::Io(error) => write!(f, "couldn't read the items: {error}"),
+            RegistryError::Json(message) => write!(f, "invalid JSON: {message}"),
+            RegistryError::NotAList => write!(f, "expected an array of items"),
+            RegistryError::AlreadyRegistered(name) => {
+                write!(f, "the type '{name}' is registered already")
+            }
+            RegistryError::Untagged { index } => {
+                write!(f, "item {index} has no \"type\" to say what it is")
+            }
+            RegistryError::UnknownType {
+                index,
+                content_type,
+            } => write!(f, "item {index} is of the unknown type '{content_type}'"),
+            RegistryError::Invalid {
+                index,
+                content_type,
+                message,
+            } => write!(f, "item {index} isn't a valid {content_type}: {message}"),
+        }
+    }
+}
+
+impl Error for RegistryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RegistryError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RegistryError {
+    fn from(error: io::Error) -> Self {
+        RegistryError::Io(error)
+    }
+}
+
+/// Constructors by content-type name.
+pub struct Registry {
+    constructors: BTreeMap<String, Constructor>,
+}
+
+impl Registry {
+    /// Creates a registry that knows no types, not even articles and tweets.
+    pub fn empty() -> Self {
+        Self {
+            constructors: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a registry that knows "article" and "tweet".
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register("article", article)
+            .and_then(|registry| registry.register("tweet", tweet))
+            .expect("the built-in types have different names");
+        registry
+    }
+
+    /// Adds a content type.
+    ///
+    /// # Arguments
+    ///
+    /// * `content_type` - The name items are tagged with, usually what the type's
+    ///   `Summary::content_type` returns.
+    /// * `constructor` - Reads an item from its JSON object, including the "type" member.
+    ///
+    /// # Errors
+    ///
+    /// * `RegistryError::AlreadyRegistered` - If the name is taken; types can't be replaced.
+    pub fn register<S, F>(
+        &mut self,
+        content_type: &str,
+        constructor: F,
+    ) -> Result<&mut Self, RegistryError>
+    where
+        S: Summary + 'static,
+        F: Fn(&Json) -> Result<S, String> + 'static,
+    {
+        if self.constructors.contains_key(content_type) {
+            return Err(RegistryError::AlreadyRegistered(String::from(content_type)));
+        }
+        let constructor: Constructor =
+            Box::new(move |item| constructor(item).map(|item| Box::new(item) as Box<dyn Summary>));
+        self.constructors.insert(String::from(content_type), constructor);
+        Ok(self)
+    }
+
+    /// Returns the names of the registered types, in alphabetical order.
+    pub fn content_types(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(String::as_str)
+    }
+
+    /// Builds one item from its tagged JSON object.
+    ///
+    /// Errors are as for `parse`, with 0 as the index.
+    pub fn construct(&self, item: &Json) -> Result<Box<dyn Summary>, RegistryError> {
+        self.construct_at(0, item)
+    }
+
+    /// Builds every item of a JSON array of tagged objects, in order.
+    ///
+    /// # Errors
+    ///
+    /// * `RegistryError::Json` - If `json` isn't valid JSON.
+    /// * `RegistryError::NotAList` - If it isn't an array.
+    /// * `RegistryError::Untagged`, `UnknownType` or `Invalid` - For the first item that can't be
+    ///   built.
+    pub fn parse(&self, json: &str) -> Result<Vec<Box<dyn Summary>>, RegistryError> {
+        let json = Json::parse(json).map_err(RegistryError::Json)?;
+        let items = json.as_array().ok_or(RegistryError::NotAList)?;
+        items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| self.construct_at(index, item))
+            .collect()
+    }
+
+    /// Reads a JSON file of tagged items from disk. See `parse`.
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<Vec<Box<dyn Summary>>, RegistryError> {
+        self.parse(&fs::read_to_string(path)?)
+    }
+
+    fn construct_at(&self, index: usize, item: &Json) -> Result<Box<dyn Summary>, RegistryError> {
+        let content_type = item
+            .get("type")
+            .and_then(Json::as_str)
+            .ok_or(RegistryError::Untagged { index })?;
+        let constructor =
+            self.constructors
+                .get(content_type)
+                .ok_or_else(|| RegistryError::UnknownType {
+                    index,
+                    content_type: String::from(content_type),
+                })?;
+
+        constructor(item).map_err(|message| RegistryError::Invalid {
+            index,
+            content_type: String::from(content_type),
+            message,
+        })
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn article(item: &Json) -> Result<NewsArticle, String> {
+    Ok(NewsArticle {
+        headline: String::from(item.field_str("headline")?),
+        location: String::from(item.field_str("location")?),
+        author: String::from(item.field_str("author")?),
+        content: String::from(item.field_str("content")?),
+        published_at: item.field_u64("published_at")?,
+    })
+}
+
+fn tweet(item: &Json) -> Result<Tweet, String> {
+    Ok(Tweet {
+        id: item.field_u64("id")?,
+        username: String::from(item.field_str("username")?),
+        content: String::from(item.field_str("content")?),
+        reply: item.field_bool("reply")?,
+        retweet: item.field_bool("retweet")?,
+        parent: item.get("parent").and_then(Json::as_u64),
+        quoted: item.get("quoted").and_then(Json::as_u64),
+        published_at: item.field_u64("published_at")?,
+    })
+}
@@ -0,0 +1,153 @@
+//! Picking the sentences that say the most, to summarize long text.
+//!
+//! This is extractive summarization by word frequency: words that come up often in a text are
+//! taken to be what it's about, so sentences full of them make the best summary. Common words
+//! like "the" say nothing about the text and aren't counted.
+
+use std::collections::HashMap;
+
+/// Words too common to tell what a text is about.
+const STOP_WORDS: [&str; 48] = [
+    "a", "about", "after", "all", "an", "and", "are", "as", "at", "be", "been", "but", "by",
+    "for", "from", "had", "has", "have", "he", "her", "his", "in", "into", "is", "it", "its",
+    "more", "not", "of", "on", "once", "or", "our", "she", "so", "than", "that", "the", "their",
+    "they", "this", "to", "was", "we", "were", "will", "with", "you",
+];
+
+/// Abbreviations whose period doesn't end a sentence.
+const ABBREVIATIONS: [&str; 9] = ["dr", "e.g", "etc", "i.e", "jr", "mr", "mrs", "ms", "st"];
+
+/// Splits `text` into sentences, each trimmed and ending with its punctuation.
+///
+/// A sentence ends at '.', '!' or '?' (and any closing quotes or brackets after it) followed by
+/// whitespace, unless the period belongs to an abbreviation or an initial, or the next word
+/// starts in lowercase. Text after the last such mark is a sentence of its own.
+pub fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if !matches!(c, '.' | '!' | '?') {
+            continue;
+        }
+        let mut end = index + c.len_utf8();
+        while let Some(&(next, closing)) = chars.peek() {
+            if !matches!(closing, '.' | '!' | '?' | '"' | '\'' | ')' | ']' | '”' | '’') {
+                break;
+            }
+            end = next + closing.len_utf8();
+            chars.next();
+        }
+
+        let rest = &text[end..];
+        let next_word = rest.trim_start();
+        let ends_here = rest.is_empty()
+            || (rest.starts_with(char::is_whitespace)
+                && !next_word.starts_with(char::is_lowercase)
+                && !(c == '.' && is_abbreviation(&text[start..index])));
+        if ends_here {
+            push_sentence(&mut sentences, &text[start..end]);
+            start = end;
+        }
+    }
+
+    push_sentence(&mut sentences, &text[start..]);
+    sentences
+}
+
+fn push_sentence<'a>(sentences: &mut Vec<&'a str>, sentence: &'a str) {
+    let sentence = sentence.trim();
+    if !sentence.is_empty() {
+        sentences.push(sentence);
+    }
+}
+
+/// Whether the word just before a period, at the end of `before`, is an abbreviation or initial.
+fn is_abbreviation(before: &str) -> bool {
+    let word = before.rsplit(char::is_whitespace).next().unwrap_or(before);
+    let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    let is_initial = word.chars().count() == 1 && word.starts_with(char::is_uppercase);
+    is_initial || ABBREVIATIONS.contains(&word.to_lowercase().as_str())
+}
+
+/// The words of `text` that count toward its subject, in lowercase.
+fn content_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| word.chars().count() > 1 && !STOP_WORDS.contains(&word.as_str()))
+}
+
+/// Scores each sentence of `text` by how frequent its words are in the whole text.
+///
+/// A word's weight is how often it appears, relative to the most frequent word, so weights go
+/// up to 1. A sentence scores the average weight of its words, which keeps long sentences from
+/// winning just by being long.
+///
+/// # Returns
+///
+/// * The sentences in their original order, each with its score.
+pub fn score_sentences(text: &str) -> Vec<(&str, f64)> {
+    let mut frequencies: HashMap<String, usize> = HashMap::new();
+    for word in content_words(text) {
+        *frequencies.entry(word).or_default() += 1;
+    }
+    let most_frequent = frequencies.values().copied().max().unwrap_or(1) as f64;
+
+    sentences(text)
+        .into_iter()
+        .map(|sentence| {
+            let weights: Vec<f64> = content_words(sentence)
+                .map(|word| frequencies[&word] as f64 / most_frequent)
+                .collect();
+            let score = if weights.is_empty() {
+                0.0
+            } else {
+                weights.iter().sum::<f64>() / weights.len() as f64
+            };
+            (sentence, score)
+        })
+        .collect()
+}
+
+/// Summarizes `text` with its best sentences.
+///
+/// Sentences are taken from the highest score down, skipping any that would push the summary
+/// past `max_chars`, until `max_sentences` are picked. They're then put back in their original
+/// order and joined with spaces. Sentences with equal scores are picked in reading order.
+///
+/// # Arguments
+///
+/// * `text` - The text to summarize.
+/// * `max_sentences` - How many sentences the summary may have at most.
+/// * `max_chars` - How long the summary may be, in characters.
+///
+/// # Returns
+///
+/// * The summary, or an empty string if not even one sentence fits.
+pub fn extract(text: &str, max_sentences: usize, max_chars: usize) -> String {
+    let scored = score_sentences(text);
+    let mut ranked: Vec<usize> = (0..scored.len()).collect();
+    ranked.sort_by(|&a, &b| scored[b].1.total_cmp(&scored[a].1).then(a.cmp(&b)));
+
+    let mut picked = Vec::new();
+    let mut length = 0;
+    for index in ranked {
+        if picked.len() == max_sentences {
+            break;
+        }
+        let sentence_length = scored[index].0.chars().count();
+        let separator = usize::from(!picked.is_empty());
+        if length + separator + sentence_length <= max_chars {
+            length += separator + sentence_length;
+            picked.push(index);
+        }
+    }
+
+    picked.sort_unstable();
+    picked
+        .iter()
+        .map(|&index| scored[index].0)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,421 @@
+//! Exporting articles and tweets as RSS 2.0 or Atom feeds, and reading them back.
+//!
+//! Each item carries its type as a category (`article` or `tweet`), so a feed written here comes
+//! back as the same articles and tweets. Items from other feeds, without that category, are read
+//! as articles.
+
+mod xml;
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{dates, truncate_at_word, NewsArticle, Summary, Tweet};
+
+use self::xml::Element;
+
+const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+
+/// The category scheme that records what type an item is.
+const TYPE_SCHEME: &str = "type";
+const LOCATION_SCHEME: &str = "location";
+const FLAG_SCHEME: &str = "flag";
+/// Category schemes for the ids of a tweet and of the tweets it refers to.
+const ID_SCHEME: &str = "id";
+const PARENT_SCHEME: &str = "parent";
+const QUOTED_SCHEME: &str = "quoted";
+
+/// How long the title of a tweet may get; the full text is in the item's content.
+const TWEET_TITLE_LENGTH: usize = 60;
+
+/// An item of a feed: one of the content types the feed knows how to write and read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry {
+    Article(NewsArticle),
+    Tweet(Tweet),
+}
+
+impl Summary for Entry {
+    fn summarize_author(&self) -> String {
+        match self {
+            Entry::Article(article) => article.summarize_author(),
+            Entry::Tweet(tweet) => tweet.summarize_author(),
+        }
+    }
+
+    fn summarize(&self) -> String {
+        match self {
+            Entry::Article(article) => article.summarize(),
+            Entry::Tweet(tweet) => tweet.summarize(),
+        }
+    }
+
+    fn summarize_within(&self, max_chars: usize) -> String {
+        match self {
+            Entry::Article(article) => article.summarize_within(max_chars),
+            Entry::Tweet(tweet) => tweet.summarize_within(max_chars),
+        }
+    }
+
+    fn published_at(&self) -> Option<u64> {
+        match self {
+            Entry::Article(article) => article.published_at(),
+            Entry::Tweet(tweet) => tweet.published_at(),
+        }
+    }
+
+    fn content_type(&self) -> &str {
+        match self {
+            Entry::Article(article) => article.content_type(),
+            Entry::Tweet(tweet) => tweet.content_type(),
+        }
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        match self {
+            Entry::Article(article) => article.field(name),
+            Entry::Tweet(tweet) => tweet.field(name),
+        }
+    }
+}
+
+impl From<NewsArticle> for Entry {
+    fn from(article: NewsArticle) -> Self {
+        Entry::Article(article)
+    }
+}
+
+impl From<Tweet> for Entry {
+    fn from(tweet: Tweet) -> Self {
+        Entry::Tweet(tweet)
+    }
+}
+
+/// The feed as a whole: what it's called, where it lives and what it's about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Channel {
+    pub title: String,
+    pub link: String,
+    pub description: String,
+}
+
+#[derive(Debug)]
+pub enum FeedError {
+    Io(io::Error),
+    /// The document isn't well-formed XML; `position` is the byte offset of the problem.
+    Xml { position: usize, message: String },
+    /// The XML is fine, but it's not a feed this module understands.
+    Format(String),
+}
+
+impl fmt::Display for FeedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FeedError::Io(error) => write!(f, "couldn't read the feed: {error}"),
+            FeedError::Xml { position, message } => {
+                write!(f, "invalid XML at byte {position}: {message}")
+            }
+            FeedError::Format(message) => write!(f, "invalid feed: {message}"),
+        }
+    }
+}
+
+impl Error for FeedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FeedError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FeedError {
+    fn from(error: io::Error) -> Self {
+        FeedError::Io(error)
+    }
+}
+
+/// Writes the entries as an RSS 2.0 document.
+///
+/// # Arguments
+///
+/// * `channel` - The title, link and description of the feed.
+/// * `entries` - The items, in the order they should appear.
+pub fn to_rss(channel: &Channel, entries: &[Entry]) -> String {
+    let mut rss_channel = Element::new("channel")
+        .child(Element::new("title").text(&channel.title))
+        .child(Element::new("link").text(&channel.link))
+        .child(Element::new("description").text(&channel.description));
+
+    for entry in entries {
+        let fields = Fields::from(entry);
+        let mut item = Element::new("item")
+            .child(Element::new("title").text(&fields.title))
+            .child(Element::new("author").text(fields.author))
+            .child(Element::new("description").text(&fields.content))
+            .child(Element::new("pubDate").text(&dates::to_rfc822(fields.published_at)));
+        for (scheme, term) in fields.categories {
+            item = item.child(Element::new("category").attribute("domain", scheme).text(&term));
+        }
+        rss_channel = rss_channel.child(item);
+    }
+
+    Element::new("rss")
+        .attribute("version", "2.0")
+        .child(rss_channel)
+        .to_document()
+}
+
+/// Writes the entries as an Atom document.
+///
+/// The feed's `updated` time is the newest entry's, and entries get ids made from the channel's
+/// link and their position in the feed.
+///
+/// # Arguments
+///
+/// * `channel` - The title, link and description of the feed.
+/// * `entries` - The items, in the order they should appear.
+pub fn to_atom(channel: &Channel, entries: &[Entry]) -> String {
+    let updated = entries.iter().filter_map(Summary::published_at).max().unwrap_or(0);
+    let mut feed = Element::new("feed")
+        .attribute("xmlns", ATOM_NAMESPACE)
+        .child(Element::new("id").text(&channel.link))
+        .child(Element::new("title").text(&channel.title))
+        .child(Element::new("subtitle").text(&channel.description))
+        .child(Element::new("link").attribute("href", &channel.link))
+        .child(Element::new("updated").text(&dates::to_rfc3339(updated)));
+
+    for (index, entry) in entries.iter().enumerate() {
+        let fields = Fields::from(entry);
+        let kind = fields.category(TYPE_SCHEME).unwrap_or_default();
+        let id = format!("{}#{kind}-{}", channel.link.trim_end_matches('/'), index + 1);
+        let published_at = dates::to_rfc3339(fields.published_at);
+
+        let mut atom_entry = Element::new("entry")
+            .child(Element::new("id").text(&id))
+            .child(Element::new("title").text(&fields.title))
+            .child(Element::new("author").child(Element::new("name").text(fields.author)))
+            .child(Element::new("content").attribute("type", "text").text(&fields.content))
+            .child(Element::new("published").text(&published_at))
+            .child(Element::new("updated").text(&published_at));
+        for (scheme, term) in fields.categories {
+            atom_entry = atom_entry.child(
+                Element::new("category")
+                    .attribute("scheme", scheme)
+                    .attribute("term", &term),
+            );
+        }
+        feed = feed.child(atom_entry);
+    }
+
+    feed.to_document()
+}
+
+/// Reads the entries of an RSS 2.0 or Atom document, telling them apart by the root element.
+pub fn parse(document: &str) -> Result<Vec<Entry>, FeedError> {
+    let root = xml::parse(document)
+        .map_err(|(position, message)| FeedError::Xml { position, message })?;
+
+    match root.name.as_str() {
+        "rss" => parse_rss(&root),
+        "feed" => parse_atom(&root),
+        other => Err(FeedError::Format(format!(
+            "expected an <rss> or <feed> document, found <{other}>"
+        ))),
+    }
+}
+
+/// Reads a feed file from disk. See `parse`.
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<Entry>, FeedError> {
+    parse(&fs::read_to_string(path)?)
+}
+
+/// The fields every format stores for an item. The item's type is one of its categories.
+struct Fields<'a> {
+    title: String,
+    author: &'a str,
+    content: String,
+    published_at: u64,
+    /// `(scheme, term)` pairs.
+    categories: Vec<(&'a str, String)>,
+}
+
+impl Fields<'_> {
+    fn category(&self, scheme: &str) -> Option<&str> {
+        self.categories
+            .iter()
+            .find(|(category_scheme, _)| *category_scheme == scheme)
+            .map(|(_, term)| term.as_str())
+    }
+
+    fn has_category(&self, scheme: &str, term: &str) -> bool {
+        self.categories
+            .iter()
+            .any(|(category_scheme, category_term)| {
+                *category_scheme == scheme && category_term == term
+            })
+    }
+
+    /// The tweet id recorded under `scheme`, if there is one.
+    fn tweet_id(&self, scheme: &str) -> Result<Option<u64>, FeedError> {
+        self.category(scheme)
+            .map(|id| {
+                id.parse()
+                    .map_err(|_| FeedError::Format(format!("invalid tweet {scheme} '{id}'")))
+            })
+            .transpose()
+    }
+
+    fn into_entry(self) -> Result<Entry, FeedError> {
+        match self.category(TYPE_SCHEME) {
+            Some("tweet") => Ok(Entry::Tweet(Tweet {
+                id: self.tweet_id(ID_SCHEME)?.unwrap_or_default(),
+                parent: self.tweet_id(PARENT_SCHEME)?,
+                quoted: self.tweet_id(QUOTED_SCHEME)?,
+                username: String::from(self.author.trim_start_matches('@')),
+                reply: self.has_category(FLAG_SCHEME, "reply"),
+                retweet: self.has_category(FLAG_SCHEME, "retweet"),
+                content: self.content,
+                published_at: self.published_at,
+            })),
+            Some("article") | None => Ok(Entry::Article(NewsArticle {
+                location: String::from(self.category(LOCATION_SCHEME).unwrap_or_default()),
+                headline: self.title,
+                author: String::from(self.author),
+                content: self.content,
+                published_at: self.published_at,
+            })),
+            Some(other) => Err(FeedError::Format(format!("unknown item type '{other}'"))),
+        }
+    }
+}
+
+impl<'a> From<&'a Entry> for Fields<'a> {
+    fn from(entry: &'a Entry) -> Self {
+        match entry {
+            Entry::Article(article) => Fields {
+                title: article.headline.clone(),
+                author: &article.author,
+                content: article.content.clone(),
+                published_at: article.published_at,
+                categories: vec![
+                    (TYPE_SCHEME, String::from("article")),
+                    (LOCATION_SCHEME, article.location.clone()),
+                ],
+            },
+            Entry::Tweet(tweet) => {
+                let mut categories = vec![
+                    (TYPE_SCHEME, String::from("tweet")),
+                    (ID_SCHEME, tweet.id.to_string()),
+                ];
+                if tweet.reply {
+                    categories.push((FLAG_SCHEME, String::from("reply")));
+                }
+                if tweet.retweet {
+                    categories.push((FLAG_SCHEME, String::from("retweet")));
+                }
+                if let Some(parent) = tweet.parent {
+                    categories.push((PARENT_SCHEME, parent.to_string()));
+                }
+                if let Some(quoted) = tweet.quoted {
+                    categories.push((QUOTED_SCHEME, quoted.to_string()));
+                }
+
+                Fields {
+                    title: truncate_at_word(&tweet.content, TWEET_TITLE_LENGTH),
+                    author: &tweet.username,
+                    content: tweet.content.clone(),
+                    published_at: tweet.published_at,
+                    categories,
+                }
+            }
+        }
+    }
+}
+
+fn parse_rss(root: &Element) -> Result<Vec<Entry>, FeedError> {
+    let channel = root
+        .find("channel")
+        .ok_or_else(|| FeedError::Format(String::from("<rss> has no <channel>")))?;
+
+    channel
+        .find_all("item")
+        .map(|item| {
+            let published_at = match item.child_text("pubDate") {
+                Some(date) => dates::parse_rfc822(date)
+                    .ok_or_else(|| FeedError::Format(format!("invalid pubDate '{date}'")))?,
+                None => 0,
+            };
+            let categories = item
+                .find_all("category")
+                .map(|category| {
+                    let scheme = category.get_attribute("domain").unwrap_or_default();
+                    (scheme, String::from(category.text.trim()))
+                })
+                .collect();
+
+            Fields {
+                title: String::from(item.child_text("title").unwrap_or_default()),
+                // Many feeds name the author with Dublin Core instead
+                author: item
+                    .child_text("author")
+                    .or_else(|| item.child_text("creator"))
+                    .unwrap_or_default(),
+                content: String::from(item.child_text("description").unwrap_or_default()),
+                published_at,
+                categories,
+            }
+            .into_entry()
+        })
+        .collect()
+}
+
+/// The text of an Atom text construct, such as a title or content, as plain text.
+fn atom_text(element: &Element) -> String {
+    match element.get_attribute("type") {
+        Some("html") => xml::html_to_text(element.text.trim()),
+        _ => String::from(element.text.trim()),
+    }
+}
+
+fn parse_atom(root: &Element) -> Result<Vec<Entry>, FeedError> {
+    root.find_all("entry")
+        .map(|entry| {
+            let date = entry
+                .child_text("published")
+                .or_else(|| entry.child_text("updated"));
+            let published_at = match date {
+                Some(date) => dates::parse_rfc3339(date)
+                    .ok_or_else(|| FeedError::Format(format!("invalid date '{date}'")))?,
+                None => 0,
+            };
+            let categories = entry
+                .find_all("category")
+                .map(|category| {
+                    (
+                        category.get_attribute("scheme").unwrap_or_default(),
+                        String::from(category.get_attribute("term").unwrap_or_default()),
+                    )
+                })
+                .collect();
+
+            Fields {
+                title: entry.find("title").map(atom_text).unwrap_or_default(),
+                author: entry
+                    .find("author")
+                    .and_then(|author| author.child_text("name"))
+                    .unwrap_or_default(),
+                content: entry
+                    .find("content")
+                    .or_else(|| entry.find("summary"))
+                    .map(atom_text)
+                    .unwrap_or_default(),
+                published_at,
+                categories,
+            }
+            .into_entry()
+        })
+        .collect()
+}
@@ -0,0 +1,327 @@
+//! Just enough XML to read and write feeds: elements, attributes, text, CDATA and entities.
+//!
+//! Namespace prefixes are dropped, so `<atom:link>` and `<link>` are the same element. DTDs,
+//! processing instructions and comments are skipped.
+
+use std::fmt::Write;
+
+/// How deeply elements may nest. Feeds need a handful of levels; the limit keeps a hostile
+/// document from overflowing the stack, since each level is read by a recursive call.
+pub(crate) const MAX_DEPTH: usize = 64;
+
+/// An element with its attributes, text and child elements.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct Element {
+    /// The local name, without a namespace prefix.
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    /// All the text directly inside the element, with entities decoded.
+    pub text: String,
+}
+
+impl Element {
+    pub fn new(name: &str) -> Element {
+        Element {
+            name: String::from(name),
+            ..Element::default()
+        }
+    }
+
+    /// Builder-style: adds an attribute.
+    pub fn attribute(mut self, name: &str, value: &str) -> Element {
+        self.attributes.push((String::from(name), String::from(value)));
+        self
+    }
+
+    /// Builder-style: sets the text.
+    pub fn text(mut self, text: &str) -> Element {
+        self.text = String::from(text);
+        self
+    }
+
+    /// Builder-style: adds a child element.
+    pub fn child(mut self, child: Element) -> Element {
+        self.children.push(child);
+        self
+    }
+
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| local_name(attribute) == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The first child called `name`.
+    pub fn find(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Every child called `name`.
+    pub fn find_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// The trimmed text of the first child called `name`.
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.find(name).map(|child| child.text.trim())
+    }
+
+    /// Writes the element as an indented document, with an XML declaration.
+    pub fn to_document(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        self.write(&mut xml, 0);
+        xml
+    }
+
+    fn write(&self, xml: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        let _ = write!(xml, "{indent}<{}", self.name);
+        for (name, value) in &self.attributes {
+            let _ = write!(xml, " {name}=\"{}\"", escape(value));
+        }
+
+        if self.children.is_empty() && self.text.is_empty() {
+            xml.push_str("/>\n");
+        } else if self.children.is_empty() {
+            let _ = writeln!(xml, ">{}</{}>", escape(&self.text), self.name);
+        } else {
+            xml.push_str(">\n");
+            for child in &self.children {
+                child.write(xml, depth + 1);
+            }
+            let _ = writeln!(xml, "{indent}</{}>", self.name);
+        }
+    }
+}
+
+/// Replaces the characters that can't appear literally in text or attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Parses a document and returns its root element.
+///
+/// On failure, returns the byte offset where reading stopped and what went wrong.
+pub(crate) fn parse(xml: &str) -> Result<Element, (usize, String)> {
+    let mut reader = Reader {
+        xml,
+        position: 0,
+        depth: 0,
+    };
+    reader.skip_misc()?;
+    let root = reader.element()?;
+    reader.skip_misc()?;
+
+    if reader.position < xml.len() {
+        return Err(reader.error("content after the root element"));
+    }
+    Ok(root)
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+struct Reader<'a> {
+    xml: &'a str,
+    position: usize,
+    /// How many elements enclose the one being read.
+    depth: usize,
+}
+
+impl Reader<'_> {
+    fn rest(&self) -> &str {
+        &self.xml[self.position..]
+    }
+
+    fn error(&self, message: &str) -> (usize, String) {
+        (self.position, String::from(message))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Moves past `end`, returning what came before it.
+    fn take_until(&mut self, end: &str) -> Result<&str, (usize, String)> {
+        let start = self.position;
+        let length = self.xml[start..]
+            .find(end)
+            .ok_or_else(|| self.error(&format!("expected '{end}'")))?;
+        self.position += length + end.len();
+        Ok(&self.xml[start..start + length])
+    }
+
+    /// Skips whitespace, comments, the XML declaration and DOCTYPEs around elements.
+    fn skip_misc(&mut self) -> Result<(), (usize, String)> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<?") {
+                self.take_until("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.take_until("-->")?;
+            } else if self.rest().starts_with("<!DOCTYPE") {
+                self.take_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&str, (usize, String)> {
+        let start = self.position;
+        let length = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(self.rest().len());
+        if length == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.position += length;
+        Ok(&self.xml[start..start + length])
+    }
+
+    fn element(&mut self) -> Result<Element, (usize, String)> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected an element"));
+        }
+        self.position += 1;
+
+        let qualified_name = self.name()?.to_string();
+        let mut element = Element::new(local_name(&qualified_name));
+
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.position += 2;
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.position += 1;
+                break;
+            }
+
+            let name = self.name()?.to_string();
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(self.error("expected '=' after an attribute name"));
+            }
+            self.position += 1;
+            self.skip_whitespace();
+
+            let quote = match self.rest().chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return Err(self.error("expected a quoted attribute value")),
+            };
+            self.position += 1;
+            let value = self.take_until(&quote.to_string())?;
+            let value = decode(value).map_err(|message| self.error(&message))?;
+            element.attributes.push((name, value));
+        }
+
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(self.error(&format!("'{qualified_name}' is never closed")));
+            } else if rest.starts_with("</") {
+                self.position += 2;
+                let closing = self.take_until(">")?.trim().to_string();
+                if closing != qualified_name {
+                    return Err(self.error(&format!(
+                        "'{qualified_name}' is closed by '{closing}'"
+                    )));
+                }
+                return Ok(element);
+            } else if rest.starts_with("<![CDATA[") {
+                self.position += "<![CDATA[".len();
+                let data = self.take_until("]]>")?;
+                element.text.push_str(data);
+            } else if rest.starts_with("<!--") || rest.starts_with("<?") {
+                self.skip_misc()?;
+            } else if rest.starts_with('<') {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error(&format!("nested deeper than {MAX_DEPTH} levels")));
+                }
+                self.depth += 1;
+                let child = self.element();
+                self.depth -= 1;
+                element.children.push(child?);
+            } else {
+                let length = rest.find('<').unwrap_or(rest.len());
+                let text = &rest[..length];
+                let text = decode(text).map_err(|message| self.error(&message))?;
+                element.text.push_str(&text);
+                self.position += length;
+            }
+        }
+    }
+}
+
+/// Turns escaped HTML, like the text of an Atom element with `type="html"`, into plain text:
+/// tags are dropped and entities decoded.
+pub(crate) fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = rest[start..].split_once('>').map_or("", |(_, after)| after);
+    }
+    text.push_str(rest);
+
+    // HTML knows more entities than XML; leave the text alone if it uses one of those
+    decode(&text).unwrap_or(text)
+}
+
+/// Replaces entity and character references with the characters they stand for.
+fn decode(text: &str) -> Result<String, String> {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find(';')
+            .ok_or_else(|| String::from("unterminated entity"))?;
+        let entity = &rest[start + 1..start + end];
+
+        let c = match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = if let Some(hex) = entity.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(decimal) = entity.strip_prefix('#') {
+                    decimal.parse().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32)
+                    .ok_or_else(|| format!("unknown entity '&{entity};'"))?
+            }
+        };
+
+        decoded.push(c);
+        rest = &rest[start + end + 1..];
+    }
+
+    decoded.push_str(rest);
+    Ok(decoded)
+}
@@ -0,0 +1,330 @@
+//! Summaries written as templates, per content type and per locale.
+//!
+//! A template is text with placeholders, like "{headline}, by {author} ({location})". Every item
+//! knows "author", "type", "summary" and "date"; other names are looked up with `Summary::field`.
+//! A placeholder can ask for emphasis with `{headline:strong}` or `{author:em}`, which only shows
+//! in Markdown and HTML. Write `{{` and `}}` for literal braces.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::aggregator::format_timestamp;
+use crate::Summary;
+
+/// The content type whose templates are used for types without their own.
+const FALLBACK_TYPE: &str = "item";
+
+/// The English "item" template, which `Summary::summarize` renders by default.
+pub(crate) const ITEM_TEMPLATE: &str = "(Read more from {author}...)";
+
+/// What a template renders to. Text from the template and from the item is escaped to match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    PlainText,
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// The template itself is malformed, at this byte offset.
+    Syntax { position: usize, message: String },
+    /// The item has no value for a placeholder of the template.
+    MissingPlaceholder {
+        placeholder: String,
+        content_type: String,
+    },
+    /// No template fits the content type, in the locale or any it falls back to.
+    NoTemplate {
+        content_type: String,
+        locale: String,
+    },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemplateError::Syntax { position, message } => {
+                write!(f, "bad template at byte {position}: {message}")
+            }
+            TemplateError::MissingPlaceholder {
+                placeholder,
+                content_type,
+            } => write!(f, "an item of type '{content_type}' has nothing for '{{{placeholder}}}'"),
+            TemplateError::NoTemplate {
+                content_type,
+                locale,
+            } => write!(f, "there is no template for type '{content_type}' in '{locale}'"),
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+/// How a placeholder's value is emphasized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emphasis {
+    None,
+    Em,
+    Strong,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Placeholder { name: String, emphasis: Emphasis },
+}
+
+/// A parsed template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parses a template.
+    ///
+    /// # Errors
+    ///
+    /// * `TemplateError::Syntax` - If a brace isn't closed or doubled, a placeholder has no name,
+    ///   or its emphasis isn't "em" or "strong".
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        let syntax = |position: usize, message: &str| TemplateError::Syntax {
+            position,
+            message: String::from(message),
+        };
+
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((position, c)) = chars.next() {
+            match c {
+                '{' if chars.peek().is_some_and(|&(_, next)| next == '{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek().is_some_and(|&(_, next)| next == '}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err(syntax(position, "'}' without '{'; write '}}' for a brace")),
+                '{' => {
+                    let rest = &source[position + 1..];
+                    let length = rest
+                        .find('}')
+                        .ok_or_else(|| syntax(position, "'{' is never closed"))?;
+                    let inside = &rest[..length];
+                    let (name, emphasis) = match inside.split_once(':') {
+                        None => (inside, Emphasis::None),
+                        Some((name, "em")) => (name, Emphasis::Em),
+                        Some((name, "strong")) => (name, Emphasis::Strong),
+                        Some(_) => return Err(syntax(position, "emphasis is 'em' or 'strong'")),
+                    };
+                    let name = name.trim();
+                    if name.is_empty() || name.contains('{') {
+                        return Err(syntax(position, "expected a placeholder name"));
+                    }
+
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Placeholder {
+                        name: String::from(name),
+                        emphasis,
+                    });
+                    while chars.next_if(|&(index, _)| index <= position + length + 1).is_some() {}
+                }
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Ok(Self { segments })
+    }
+
+    /// The names of the placeholders, in order of appearance.
+    pub fn placeholders(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Placeholder { name, .. } => Some(name.as_str()),
+            Segment::Text(_) => None,
+        })
+    }
+
+    /// Fills in the template from `item`.
+    ///
+    /// # Errors
+    ///
+    /// * `TemplateError::MissingPlaceholder` - If `item` has no value for a placeholder.
+    pub fn render(
+        &self,
+        item: &(impl Summary + ?Sized),
+        format: Format,
+    ) -> Result<String, TemplateError> {
+        let mut rendered = String::new();
+
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => rendered.push_str(&escape(text, format)),
+                Segment::Placeholder { name, emphasis } => {
+                    let value = lookup(item, name).ok_or_else(|| {
+                        TemplateError::MissingPlaceholder {
+                            placeholder: name.clone(),
+                            content_type: String::from(item.content_type()),
+                        }
+                    })?;
+                    let value = escape(&value, format);
+                    let (open, close) = match (format, emphasis) {
+                        (Format::PlainText, _) | (_, Emphasis::None) => ("", ""),
+                        (Format::Markdown, Emphasis::Em) => ("*", "*"),
+                        (Format::Markdown, Emphasis::Strong) => ("**", "**"),
+                        (Format::Html, Emphasis::Em) => ("<em>", "</em>"),
+                        (Format::Html, Emphasis::Strong) => ("<strong>", "</strong>"),
+                    };
+                    rendered.push_str(open);
+                    rendered.push_str(&value);
+                    rendered.push_str(close);
+                }
+            }
+        }
+
+        Ok(rendered)
+    }
+}
+
+/// The value of a placeholder: one every item has, or one of the item's fields.
+fn lookup(item: &(impl Summary + ?Sized), name: &str) -> Option<String> {
+    match name {
+        "author" => Some(item.summarize_author()),
+        "type" => Some(String::from(item.content_type())),
+        "summary" => Some(item.summarize()),
+        "date" => item.published_at().map(format_timestamp),
+        _ => item.field(name),
+    }
+}
+
+fn escape(text: &str, format: Format) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match (format, c) {
+            (Format::Markdown, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#') => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            (Format::Html, '&') => escaped.push_str("&amp;"),
+            (Format::Html, '<') => escaped.push_str("&lt;"),
+            (Format::Html, '>') => escaped.push_str("&gt;"),
+            (Format::Html, '"') => escaped.push_str("&quot;"),
+            (Format::Html, '\'') => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Templates by content type and locale.
+///
+/// Locales are tags like "en" or "fr-CA". Looking up a template tries the type in the locale, in
+/// its language ("fr" for "fr-CA") and in the default locale, then does the same for "item", so
+/// an "item" template in the default locale catches everything.
+#[derive(Debug, Clone)]
+pub struct Templates {
+    default_locale: String,
+    templates: HashMap<(String, String), Template>,
+}
+
+impl Templates {
+    /// Creates an empty set of templates, falling back to `default_locale`.
+    pub fn new(default_locale: &str) -> Self {
+        Self {
+            default_locale: String::from(default_locale),
+            templates: HashMap::new(),
+        }
+    }
+
+    /// Parses and adds the template for `content_type` in `locale`, replacing any before it.
+    ///
+    /// # Arguments
+    ///
+    /// * `content_type` - A name as returned by `Summary::content_type`, or "item" for any type.
+    /// * `locale` - A locale tag, like "en" or "fr-CA".
+    /// * `source` - The template.
+    ///
+    /// # Errors
+    ///
+    /// * `TemplateError::Syntax` - If `source` isn't a valid template.
+    pub fn add(
+        &mut self,
+        content_type: &str,
+        locale: &str,
+        source: &str,
+    ) -> Result<&mut Self, TemplateError> {
+        let template = Template::parse(source)?;
+        self.templates
+            .insert((String::from(content_type), String::from(locale)), template);
+        Ok(self)
+    }
+
+    /// Returns the template to use for `content_type` in `locale`, following the fallbacks.
+    pub fn get(&self, content_type: &str, locale: &str) -> Option<&Template> {
+        let mut locales = vec![locale];
+        if let Some((language, _)) = locale.split_once(['-', '_']) {
+            locales.push(language);
+        }
+        locales.push(&self.default_locale);
+
+        [content_type, FALLBACK_TYPE].iter().find_map(|content_type| {
+            locales.iter().find_map(|locale| {
+                self.templates
+                    .get(&(String::from(*content_type), String::from(*locale)))
+            })
+        })
+    }
+
+    /// Summarizes `item` with its template for `locale`.
+    ///
+    /// # Errors
+    ///
+    /// * `TemplateError::NoTemplate` - If no template fits.
+    /// * `TemplateError::MissingPlaceholder` - If `item` has no value for a placeholder.
+    pub fn render(
+        &self,
+        item: &(impl Summary + ?Sized),
+        locale: &str,
+        format: Format,
+    ) -> Result<String, TemplateError> {
+        let template = self
+            .get(item.content_type(), locale)
+            .ok_or_else(|| TemplateError::NoTemplate {
+                content_type: String::from(item.content_type()),
+                locale: String::from(locale),
+            })?;
+        template.render(item, format)
+    }
+}
+
+/// English and French templates. The English "item" template is the one `summarize` uses by
+/// default.
+impl Default for Templates {
+    fn default() -> Self {
+        let mut templates = Self::new("en");
+        for (content_type, locale, source) in [
+            ("item", "en", ITEM_TEMPLATE),
+            ("article", "en", "{headline:strong}, by {author} ({location})"),
+            ("tweet", "en", "{author}: {content}"),
+            ("thread", "en", "{author}: {content} ({replies} replies)"),
+            ("item", "fr", "(Lire la suite de {author}...)"),
+            ("article", "fr", "{headline:strong}, par {author} ({location})"),
+            ("thread", "fr", "{author} : {content} ({replies} réponses)"),
+        ] {
+            templates
+                .add(content_type, locale, source)
+                .expect("the built-in templates are valid");
+        }
+        templates
+    }
+}
@@ -0,0 +1,356 @@
+//! Putting conversations back together from tweets that reply to and retweet each other.
+//!
+//! A tweet's `parent` is the tweet it replies to when `reply` is set, or the tweet it retweets
+//! when `retweet` is set. Following parents gives threads of replies and chains of retweets.
+//! Parents may be missing from the timeline, and bad data may even make parents loop; both are
+//! reported instead of looping forever.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use crate::{truncate_at_word, Summary, Tweet};
+
+/// How much of the first tweet a thread's summary quotes.
+const THREAD_SUMMARY_LENGTH: usize = 80;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThreadError {
+    UnknownTweet(u64),
+    /// `tweet` refers to `parent`, which isn't in the timeline.
+    MissingParent { tweet: u64, parent: u64 },
+    /// Following parents leads back around to these tweets.
+    Cycle(Vec<u64>),
+}
+
+impl fmt::Display for ThreadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThreadError::UnknownTweet(id) => write!(f, "there is no tweet {id}"),
+            ThreadError::MissingParent { tweet, parent } => {
+                write!(f, "tweet {tweet} refers to tweet {parent}, which is missing")
+            }
+            ThreadError::Cycle(ids) => {
+                let ids: Vec<String> = ids.iter().map(u64::to_string).collect();
+                write!(f, "tweets {} refer to each other in a loop", ids.join(" -> "))
+            }
+        }
+    }
+}
+
+impl Error for ThreadError {}
+
+/// A set of tweets, indexed by id and by what they reply to and retweet.
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    tweets: BTreeMap<u64, Tweet>,
+    /// The replies to each tweet, oldest first.
+    replies: HashMap<u64, Vec<u64>>,
+    /// The direct retweets of each tweet, oldest first.
+    retweets: HashMap<u64, Vec<u64>>,
+}
+
+impl Timeline {
+    /// Indexes the tweets. Of several tweets with the same id, the last one is kept.
+    pub fn new(tweets: impl IntoIterator<Item = Tweet>) -> Self {
+        let tweets: BTreeMap<u64, Tweet> =
+            tweets.into_iter().map(|tweet| (tweet.id, tweet)).collect();
+        let mut replies: HashMap<u64, Vec<u64>> = HashMap::new();
+        let mut retweets: HashMap<u64, Vec<u64>> = HashMap::new();
+
+        let mut by_time: Vec<&Tweet> = tweets.values().collect();
+        by_time.sort_by_key(|tweet| (tweet.published_at, tweet.id));
+        for tweet in by_time {
+            if let Some(parent) = reply_parent(tweet) {
+                replies.entry(parent).or_default().push(tweet.id);
+            }
+            if let Some(parent) = retweet_parent(tweet) {
+                retweets.entry(parent).or_default().push(tweet.id);
+            }
+        }
+
+        Self {
+            tweets,
+            replies,
+            retweets,
+        }
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Tweet> {
+        self.tweets.get(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tweets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tweets.is_empty()
+    }
+
+    /// Returns the direct replies to a tweet, oldest first.
+    pub fn replies_to(&self, id: u64) -> Vec<&Tweet> {
+        self.lookup(self.replies.get(&id))
+    }
+
+    /// Returns the tweets that quote a tweet, by id.
+    pub fn quotes_of(&self, id: u64) -> Vec<&Tweet> {
+        self.tweets
+            .values()
+            .filter(|tweet| tweet.quoted == Some(id))
+            .collect()
+    }
+
+    /// Returns the replies leading to a tweet, from the start of the conversation down to it.
+    ///
+    /// # Errors
+    ///
+    /// * `ThreadError::UnknownTweet` - If there's no tweet `id`.
+    /// * `ThreadError::MissingParent` - If a reply on the way up refers to a missing tweet.
+    /// * `ThreadError::Cycle` - If the replies loop.
+    pub fn conversation(&self, id: u64) -> Result<Vec<&Tweet>, ThreadError> {
+        let mut path = self.follow(id, reply_parent)?;
+        path.reverse();
+        Ok(path)
+    }
+
+    /// Returns the chain of retweets from a tweet back to the original tweet, which comes last.
+    ///
+    /// A tweet that isn't a retweet is a chain of its own. The errors are as for `conversation`.
+    pub fn retweet_chain(&self, id: u64) -> Result<Vec<&Tweet>, ThreadError> {
+        self.follow(id, retweet_parent)
+    }
+
+    /// Returns every retweet of a tweet, including retweets of its retweets, nearest first.
+    pub fn retweets_of(&self, id: u64) -> Vec<&Tweet> {
+        let mut found = Vec::new();
+        let mut seen = HashSet::from([id]);
+        let mut next = vec![id];
+
+        while !next.is_empty() {
+            let mut later = Vec::new();
+            for id in next {
+                for &retweet in self.retweets.get(&id).into_iter().flatten() {
+                    if seen.insert(retweet) {
+                        found.push(&self.tweets[&retweet]);
+                        later.push(retweet);
+                    }
+                }
+            }
+            next = later;
+        }
+
+        found
+    }
+
+    /// Returns the thread a tweet belongs to.
+    ///
+    /// Unlike `conversation`, this doesn't fail on broken data: a thread whose first tweet
+    /// replies to a missing tweet says so with `missing_parent`, and a loop of replies is started
+    /// at its lowest id and marked `cyclic`.
+    pub fn thread(&self, id: u64) -> Result<Thread<'_>, ThreadError> {
+        let (root, missing_parent, cyclic) = match self.conversation(id) {
+            Ok(path) => (path[0].id, None, false),
+            Err(ThreadError::MissingParent { tweet, parent }) => (tweet, Some(parent), false),
+            Err(ThreadError::Cycle(ids)) => (ids.into_iter().min().unwrap_or(id), None, true),
+            Err(error) => return Err(error),
+        };
+
+        let mut replies = Vec::new();
+        let mut seen = HashSet::from([root]);
+        self.collect_replies(root, &mut seen, &mut replies);
+
+        Ok(Thread {
+            root: &self.tweets[&root],
+            replies,
+            missing_parent,
+            cyclic,
+        })
+    }
+
+    /// Returns every thread in the timeline, ordered by when their first tweets were posted.
+    ///
+    /// Every tweet that isn't a retweet appears in exactly one thread.
+    pub fn threads(&self) -> Vec<Thread<'_>> {
+        let mut threads = Vec::new();
+        let mut covered = HashSet::new();
+
+        let mut by_time: Vec<&Tweet> =
+            self.tweets.values().filter(|tweet| !tweet.retweet).collect();
+        by_time.sort_by_key(|tweet| (tweet.published_at, tweet.id));
+
+        for tweet in by_time {
+            if covered.contains(&tweet.id) {
+                continue;
+            }
+            let thread = self.thread(tweet.id).expect("the tweet is in the timeline");
+            if covered.insert(thread.root.id) {
+                covered.extend(thread.replies.iter().map(|(_, reply)| reply.id));
+                threads.push(thread);
+            }
+        }
+
+        threads.sort_by_key(|thread| (thread.root.published_at, thread.root.id));
+        threads
+    }
+
+    fn lookup(&self, ids: Option<&Vec<u64>>) -> Vec<&Tweet> {
+        ids.into_iter()
+            .flatten()
+            .map(|id| &self.tweets[id])
+            .collect()
+    }
+
+    /// Walks from `id` to its parents, as picked by `parent_of`, until a tweet without one.
+    fn follow(
+        &self,
+        id: u64,
+        parent_of: fn(&Tweet) -> Option<u64>,
+    ) -> Result<Vec<&Tweet>, ThreadError> {
+        let mut tweet = self.get(id).ok_or(ThreadError::UnknownTweet(id))?;
+        let mut path = vec![tweet];
+        let mut seen = HashSet::from([id]);
+
+        while let Some(parent) = parent_of(tweet) {
+            if !seen.insert(parent) {
+                let start = path.iter().position(|seen| seen.id == parent).unwrap_or(0);
+                return Err(ThreadError::Cycle(path[start..].iter().map(|seen| seen.id).collect()));
+            }
+            tweet = self.get(parent).ok_or(ThreadError::MissingParent {
+                tweet: tweet.id,
+                parent,
+            })?;
+            path.push(tweet);
+        }
+
+        Ok(path)
+    }
+
+    /// Adds the replies below `id` depth-first, so each reply follows the tweet it answers.
+    ///
+    /// Uses a stack of its own rather than recursion, so a thread can be as deep as it likes.
+    fn collect_replies<'a>(
+        &'a self,
+        id: u64,
+        seen: &mut HashSet<u64>,
+        replies: &mut Vec<(usize, &'a Tweet)>,
+    ) {
+        let replies_to = |id: u64| self.replies.get(&id).into_iter().flatten().rev();
+        let mut stack: Vec<(usize, u64)> = replies_to(id).map(|&reply| (1, reply)).collect();
+
+        while let Some((depth, reply)) = stack.pop() {
+            if seen.insert(reply) {
+                replies.push((depth, &self.tweets[&reply]));
+                stack.extend(replies_to(reply).map(|&below| (depth + 1, below)));
+            }
+        }
+    }
+}
+
+fn reply_parent(tweet: &Tweet) -> Option<u64> {
+    tweet.parent.filter(|_| tweet.reply)
+}
+
+fn retweet_parent(tweet: &Tweet) -> Option<u64> {
+    tweet.parent.filter(|_| tweet.retweet)
+}
+
+/// A tweet and the replies below it.
+#[derive(Debug, Clone)]
+pub struct Thread<'a> {
+    root: &'a Tweet,
+    /// Every reply with its depth below the root, in reading order.
+    replies: Vec<(usize, &'a Tweet)>,
+    missing_parent: Option<u64>,
+    cyclic: bool,
+}
+
+impl<'a> Thread<'a> {
+    /// The tweet that starts the thread.
+    pub fn root(&self) -> &'a Tweet {
+        self.root
+    }
+
+    /// The replies with their depth below the root (1 for direct replies), in reading order:
+    /// each reply comes right after the tweet it answers and the replies before it.
+    pub fn replies(&self) -> &[(usize, &'a Tweet)] {
+        &self.replies
+    }
+
+    /// The number of tweets in the thread, including the first.
+    pub fn len(&self) -> usize {
+        self.replies.len() + 1
+    }
+
+    /// A thread always has its first tweet, so it's never empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The tweet the first tweet replies to, if it's missing from the timeline.
+    pub fn missing_parent(&self) -> Option<u64> {
+        self.missing_parent
+    }
+
+    /// Whether the replies of this thread loop back to its first tweet.
+    pub fn is_cyclic(&self) -> bool {
+        self.cyclic
+    }
+
+    /// Everyone who tweeted in the thread, in order of their first tweet.
+    pub fn participants(&self) -> Vec<&'a str> {
+        let mut participants: Vec<&str> = Vec::new();
+        for tweet in self.tweets() {
+            if !participants.contains(&tweet.username.as_str()) {
+                participants.push(&tweet.username);
+            }
+        }
+        participants
+    }
+
+    /// Every tweet of the thread, in reading order.
+    pub fn tweets(&self) -> impl Iterator<Item = &'a Tweet> + '_ {
+        std::iter::once(self.root).chain(self.replies.iter().map(|(_, tweet)| *tweet))
+    }
+}
+
+/// A thread summarizes as its first tweet plus how much conversation followed.
+impl Summary for Thread<'_> {
+    fn summarize_author(&self) -> String {
+        self.root.summarize_author()
+    }
+
+    fn summarize(&self) -> String {
+        let opening = truncate_at_word(&self.root.content, THREAD_SUMMARY_LENGTH);
+        let replies = match self.replies.len() {
+            0 => return format!("{}: {opening}", self.summarize_author()),
+            1 => String::from("1 reply"),
+            count => format!("{count} replies"),
+        };
+        let others = self.participants().len() - 1;
+        let others = match others {
+            0 => String::new(),
+            1 => String::from(" from 1 other"),
+            count => format!(" from {count} others"),
+        };
+
+        format!("{}: {opening} ({replies}{others})", self.summarize_author())
+    }
+
+    /// The time of the latest tweet in the thread.
+    fn published_at(&self) -> Option<u64> {
+        self.tweets().map(|tweet| tweet.published_at).max()
+    }
+
+    fn content_type(&self) -> &str {
+        "thread"
+    }
+
+    /// The fields of the first tweet, plus "replies", the number of replies.
+    fn field(&self, name: &str) -> Option<String> {
+        match name {
+            "replies" => Some(self.replies.len().to_string()),
+            _ => self.root.field(name),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use traits_defining_shared_behavior::aggregator::{format_timestamp, Feed};
+use traits_defining_shared_behavior::{NewsArticle, Summary, Tweet};
+
+fn tweet(username: &str, published_at: u64) -> Tweet {
+    Tweet {
+        id: 1,
+        username: String::from(username),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+        parent: None,
+        quoted: None,
+        published_at,
+    }
+}
+
+fn article(author: &str, published_at: u64) -> NewsArticle {
+    NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup Championship!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from(author),
+        content: String::from("The Pittsburgh Penguins are the best hockey team in the NHL."),
+        published_at,
+    }
+}
+
+/// Content that doesn't know when it was published.
+struct Rumor;
+
+impl Summary for Rumor {
+    fn summarize_author(&self) -> String {
+        String::from("anonymous")
+    }
+}
+
+#[test]
+fn sorts_newest_first_with_undated_items_last() {
+    let mut feed = Feed::new();
+    feed.push(tweet("early", 100));
+    feed.push(Rumor);
+    feed.push(article("Iceburgh", 300));
+    feed.push(tweet("late", 200));
+    feed.sort_by_time();
+
+    let authors: Vec<String> = feed.iter().map(|item| item.summarize_author()).collect();
+    assert_eq!(authors, ["Iceburgh", "@late", "@early", "anonymous"]);
+}
+
+#[test]
+fn filters_by_author_ignoring_case_and_at_sign() {
+    let feed: Feed = vec![
+        Box::new(tweet("horse_ebooks", 1)) as Box<dyn Summary>,
+        Box::new(article("Iceburgh", 2)),
+        Box::new(tweet("horse_ebooks", 3)),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(feed.by_author("horse_ebooks").count(), 2);
+    assert_eq!(feed.by_author("@Horse_Ebooks").count(), 2);
+    assert_eq!(feed.by_author("iceburgh").count(), 1);
+    assert_eq!(feed.by_author("nobody").count(), 0);
+
+    // The results don't borrow the name they were looked up by
+    let matches = {
+        let name = String::from("HORSE_EBOOKS");
+        feed.by_author(&name)
+    };
+    assert_eq!(matches.count(), 2);
+}
+
+#[test]
+fn digest_lists_items_up_to_the_limit() {
+    let mut feed = Feed::new();
+    feed.push(article("Iceburgh", 1_722_873_600));
+    feed.push(tweet("horse_ebooks", 1_722_870_000));
+    feed.push(Rumor);
+
+    assert_eq!(
+        feed.digest(2),
+        "Digest: 3 item(s)\n\
+         - [2024-08-05 16:00] Iceburgh: Penguins win the Stanley Cup Championship!, by Iceburgh \
+         (Pittsburgh, PA, USA): The Pittsburgh Penguins are the best hockey team in the NHL.\n\
+         - [2024-08-05 15:00] @horse_ebooks: (Read more from @horse_ebooks...)\n\
+         ...and 1 more\n"
+    );
+    assert!(feed.digest(5).ends_with("- [undated] anonymous: (Read more from anonymous...)\n"));
+    assert_eq!(Feed::new().digest(5), "Digest: 0 item(s)\n");
+}
+
+#[test]
+fn formats_timestamps_as_utc() {
+    assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+    // The leap day of 2000, a year divisible by 400
+    assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    assert_eq!(format_timestamp(1_709_251_199), "2024-02-29 23:59");
+}
@@ -0,0 +1,84 @@
+use traits_defining_shared_behavior::{truncate_at_word, NewsArticle, Summary, Tweet};
+
+fn article(content: &str) -> NewsArticle {
+    NewsArticle {
+        headline: String::from("Penguins win!"),
+        location: String::from("Pittsburgh"),
+        author: String::from("Iceburgh"),
+        content: String::from(content),
+        published_at: 0,
+    }
+}
+
+#[test]
+fn article_author_comes_from_its_field() {
+    assert_eq!(article("").summarize_author(), "Iceburgh");
+}
+
+#[test]
+fn truncates_at_word_boundaries() {
+    let text = "The Pittsburgh Penguins once again are the best hockey team";
+
+    assert_eq!(truncate_at_word(text, 100), text);
+    assert_eq!(truncate_at_word(text, text.len()), text);
+    assert_eq!(truncate_at_word(text, 26), "The Pittsburgh Penguins...");
+    assert_eq!(truncate_at_word(text, 25), "The Pittsburgh...");
+    assert!(truncate_at_word(text, 40).chars().count() <= 40);
+}
+
+#[test]
+fn truncation_handles_long_words_tiny_limits_and_unicode() {
+    assert_eq!(truncate_at_word("Supercalifragilistic", 8), "Super...");
+    assert_eq!(truncate_at_word("Penguins win", 3), "...");
+    assert_eq!(truncate_at_word("Penguins win", 0), "");
+    assert_eq!(truncate_at_word("Café crème brûlée tonight", 21), "Café crème brûlée...");
+    assert_eq!(truncate_at_word("  padded  ", 6), "padded");
+}
+
+#[test]
+fn article_summary_fills_the_length_with_content() {
+    let article =
+        article("The Pittsburgh Penguins once again are the best hockey team in the NHL.");
+
+    assert_eq!(
+        article.summarize(),
+        "Penguins win!, by Iceburgh (Pittsburgh): \
+         The Pittsburgh Penguins once again are the best hockey team in the NHL."
+    );
+    assert_eq!(
+        article.summarize_within(68),
+        "Penguins win!, by Iceburgh (Pittsburgh): The Pittsburgh Penguins..."
+    );
+    // No room for the content, so only the heading is kept, shortened
+    assert_eq!(article.summarize_within(30), "Penguins win!, by Iceburgh...");
+
+    for max_chars in 0..120 {
+        assert!(article.summarize_within(max_chars).chars().count() <= max_chars);
+    }
+}
+
+#[test]
+fn long_articles_are_cut_to_the_default_length() {
+    let article = article(&"word ".repeat(100));
+    let summary = article.summarize();
+
+    assert!(summary.chars().count() <= NewsArticle::SUMMARY_LENGTH);
+    assert!(summary.ends_with("word..."));
+}
+
+#[test]
+fn other_types_truncate_their_default_summary() {
+    let tweet = Tweet {
+        id: 1,
+        username: String::from("horse_ebooks"),
+        content: String::from("of course"),
+        reply: false,
+        retweet: false,
+        parent: None,
+        quoted: None,
+        published_at: 0,
+    };
+
+    assert_eq!(tweet.summarize_within(100), "(Read more from @horse_ebooks...)");
+    assert_eq!(tweet.summarize_within(20), "(Read more from...");
+}
@@ -0,0 +1,271 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::thread;
+
+use traits_defining_shared_behavior::notifications::{
+    Dispatcher, FileSink, MemorySink, Notification, NotificationSink, NotifyError, RateLimit,
+    WebhookSink,
+};
+use traits_defining_shared_behavior::{NewsArticle, Summary, Tweet};
+
+fn tweet(username: &str, content: &str) -> Tweet {
+    Tweet {
+        id: 1,
+        username: String::from(username),
+        content: String::from(content),
+        reply: false,
+        retweet: false,
+        parent: None,
+        quoted: None,
+        published_at: 1_722_870_000,
+    }
+}
+
+fn article() -> NewsArticle {
+    NewsArticle {
+        headline: String::from("Penguins win!"),
+        location: String::from("Pittsburgh"),
+        author: String::from("Iceburgh"),
+        content: String::from("The Penguins are the best hockey team in the NHL."),
+        published_at: 1_722_873_600,
+    }
+}
+
+#[test]
+fn every_sink_hears_about_every_item_by_default() {
+    let first = MemorySink::new();
+    let second = MemorySink::new();
+    let mut dispatcher = Dispatcher::new();
+    dispatcher.subscribe(first.clone());
+    dispatcher.subscribe(second.clone());
+
+    let report = dispatcher.notify(&article());
+
+    assert_eq!(report.delivered, 2);
+    assert_eq!(first.received(), second.received());
+    assert_eq!(
+        first.received()[0].to_string(),
+        "Breaking news! Penguins win!, by Iceburgh (Pittsburgh): \
+         The Penguins are the best hockey team in the NHL."
+    );
+}
+
+#[test]
+fn sinks_can_be_limited_to_content_types() {
+    let articles = MemorySink::new();
+    let tweets = MemorySink::new();
+    let mut dispatcher = Dispatcher::new();
+    dispatcher.subscribe(articles.clone()).only(["article"]);
+    dispatcher.subscribe(tweets.clone()).only(["tweet"]);
+
+    dispatcher.notify(&article());
+    let report = dispatcher.notify(&tweet("horse_ebooks", "of course"));
+
+    assert_eq!((report.delivered, report.filtered), (1, 1));
+    assert_eq!(articles.received().len(), 1);
+    assert_eq!(tweets.received()[0].content_type, "tweet");
+    assert_eq!(tweets.received()[0].author, "@horse_ebooks");
+}
+
+#[test]
+fn repeated_tweets_are_rate_limited() {
+    let sink = MemorySink::new();
+    let mut dispatcher = Dispatcher::new();
+    dispatcher.subscribe(sink.clone()).rate_limit(RateLimit {
+        repeats: 2,
+        window: 60,
+    });
+
+    let spam = tweet("horse_ebooks", "everything happens so much");
+    assert_eq!(dispatcher.notify_at(&spam, 0).delivered, 1);
+    assert_eq!(dispatcher.notify_at(&spam, 10).delivered, 1);
+    // The same tweet twice in the last minute is the limit
+    assert_eq!(dispatcher.notify_at(&spam, 20).rate_limited, 1);
+    // Other tweets, by the same author or not, have their own allowance
+    assert_eq!(dispatcher.notify_at(&tweet("horse_ebooks", "a new one"), 25).delivered, 1);
+    assert_eq!(dispatcher.notify_at(&tweet("rustlang", "1.80 is out"), 30).delivered, 1);
+    // Once the first tweet leaves the window, there's room for another
+    assert_eq!(dispatcher.notify_at(&spam, 60).delivered, 1);
+    assert_eq!(dispatcher.notify_at(&spam, 65).rate_limited, 1);
+
+    assert_eq!(sink.received().len(), 5);
+}
+
+#[test]
+fn forgotten_items_get_their_whole_allowance_back() {
+    let sink = MemorySink::new();
+    let mut dispatcher = Dispatcher::new();
+    dispatcher.subscribe(sink.clone()).rate_limit(RateLimit {
+        repeats: 2,
+        window: 60,
+    });
+
+    let spam = tweet("horse_ebooks", "everything happens so much");
+    for at in [0, 10, 20] {
+        dispatcher.notify_at(&spam, at);
+    }
+    for at in 0..100 {
+        dispatcher.notify_at(&tweet("rustlang", &format!("release {at}")), 100 + at);
+    }
+    assert_eq!(dispatcher.notify_at(&spam, 300).delivered, 1);
+    assert_eq!(dispatcher.notify_at(&spam, 301).delivered, 1);
+    assert_eq!(dispatcher.notify_at(&spam, 302).rate_limited, 1);
+
+    assert_eq!(sink.received().len(), 104);
+}
+
+/// Fails to send the first `failures` notifications, then keeps them in `inner`.
+struct FlakySink {
+    failures: usize,
+    inner: MemorySink,
+}
+
+impl NotificationSink for FlakySink {
+    fn name(&self) -> String {
+        String::from("flaky")
+    }
+
+    fn send(&mut self, notification: &Notification) -> Result<(), NotifyError> {
+        if self.failures > 0 {
+            self.failures -= 1;
+            return Err(NotifyError::Io(io::Error::other("offline")));
+        }
+        self.inner.send(notification)
+    }
+}
+
+#[test]
+fn failed_sends_do_not_count_against_the_rate_limit() {
+    let inner = MemorySink::new();
+    let mut dispatcher = Dispatcher::new();
+    let flaky = FlakySink {
+        failures: 2,
+        inner: inner.clone(),
+    };
+    dispatcher.subscribe(flaky).rate_limit(RateLimit {
+        repeats: 1,
+        window: 60,
+    });
+
+    let news = tweet("rustlang", "1.80 is out");
+    assert_eq!(dispatcher.notify_at(&news, 0).failed.len(), 1);
+    assert_eq!(dispatcher.notify_at(&news, 1).failed.len(), 1);
+    assert_eq!(dispatcher.notify_at(&news, 2).delivered, 1);
+    assert_eq!(dispatcher.notify_at(&news, 3).rate_limited, 1);
+    assert_eq!(inner.received().len(), 1);
+}
+
+#[test]
+fn file_sink_appends_a_line_per_notification() {
+    let path = std::env::temp_dir().join(format!("notifications-{}.log", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut dispatcher = Dispatcher::new();
+    dispatcher.subscribe(FileSink::open(&path).unwrap());
+    dispatcher.notify(&tweet("horse_ebooks", "of course"));
+    dispatcher.notify(&tweet("rustlang", "1.80 is out"));
+    drop(dispatcher);
+
+    let log = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        log,
+        "Breaking news! (Read more from @horse_ebooks...)\n\
+         Breaking news! (Read more from @rustlang...)\n"
+    );
+}
+
+/// Accepts one request, answers with `status` and returns the request body.
+fn webhook_server(status: &'static str) -> (SocketAddr, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+
+        write!(stream, "HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n").unwrap();
+        String::from_utf8(body).unwrap()
+    });
+
+    (address, handle)
+}
+
+#[test]
+fn webhook_posts_json_to_localhost() {
+    let (address, server) = webhook_server("204 No Content");
+    let mut dispatcher = Dispatcher::new();
+    dispatcher.subscribe(WebhookSink::new(address, "/hooks/news").unwrap());
+
+    let report = dispatcher.notify(&tweet("rustlang", "\"zero-cost\""));
+
+    assert_eq!(report.delivered, 1, "{:?}", report.failed);
+    assert_eq!(
+        server.join().unwrap(),
+        Notification::about(&tweet("rustlang", "\"zero-cost\"")).to_json()
+    );
+    assert_eq!(
+        Notification::about(&article()).to_json(),
+        "{\"type\":\"article\",\"author\":\"Iceburgh\",\"summary\":\"Penguins win!, by Iceburgh \
+         (Pittsburgh): The Penguins are the best hockey team in the NHL.\",\
+         \"published_at\":1722873600}"
+    );
+}
+
+#[test]
+fn failing_sinks_are_reported_without_stopping_the_others() {
+    let (address, server) = webhook_server("500 Internal Server Error");
+    let memory = MemorySink::new();
+    let mut dispatcher = Dispatcher::new();
+    dispatcher.subscribe(WebhookSink::new(address, "/").unwrap());
+    dispatcher.subscribe(memory.clone());
+
+    let report = dispatcher.notify(&article());
+    server.join().unwrap();
+
+    assert_eq!(report.delivered, 1);
+    assert_eq!(memory.received().len(), 1);
+    assert!(matches!(
+        report.failed.as_slice(),
+        [(name, NotifyError::Rejected { .. })] if name.starts_with("webhook")
+    ));
+}
+
+#[test]
+fn webhooks_only_go_to_this_machine() {
+    let remote: SocketAddr = "93.184.216.34:80".parse().unwrap();
+
+    assert!(matches!(WebhookSink::new(remote, "/"), Err(NotifyError::NotLocal(_))));
+    assert!(WebhookSink::new("[::1]:8080".parse().unwrap(), "/").is_ok());
+}
+
+#[test]
+fn items_without_a_content_type_are_plain_items() {
+    struct Rumor;
+    impl Summary for Rumor {
+        fn summarize_author(&self) -> String {
+            String::from("anonymous")
+        }
+    }
+
+    let sink = MemorySink::new();
+    let mut dispatcher = Dispatcher::new();
+    dispatcher.subscribe(sink.clone()).only(["item"]);
+    dispatcher.notify(&Rumor);
+
+    assert_eq!(sink.received()[0].published_at, None);
+}
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use traits_defining_shared_behavior::pair::{OrderedPair, Pair};
+
+#[test]
+fn max_and_min_prefer_x_on_ties() {
+    let pair = Pair::new(5, 10);
+    assert_eq!(pair.max(), 10);
+    assert_eq!(pair.min(), 5);
+
+    let tie = Pair::new((1, "x"), (1, "y")).map(|(rank, name)| Rank(rank, name));
+    assert_eq!(tie.as_ref().max().1, "x");
+    assert_eq!(tie.min().1, "x");
+
+    // Floats only have a partial order; NaN can't be compared, so x is kept
+    assert_eq!(Pair::new(1.5, 0.5).max(), 1.5);
+    assert!(Pair::new(f64::NAN, 2.0).max().is_nan());
+}
+
+/// Compares by rank only, to tell equal members apart.
+#[derive(Debug)]
+struct Rank(u32, &'static str);
+
+impl PartialEq for Rank {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl PartialOrd for Rank {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+#[test]
+fn swap_map_and_as_ref() {
+    let pair = Pair::new(String::from("ferris"), String::from("corro"));
+
+    assert_eq!(pair.as_ref().map(String::len), Pair::new(6, 5));
+    assert_eq!(*pair.as_ref().max(), "ferris");
+    assert_eq!(pair.swap(), Pair::new(String::from("corro"), String::from("ferris")));
+}
+
+#[test]
+fn orders_by_key_functions() {
+    let words = Pair::new("crab", "ox");
+
+    assert_eq!(words.max_by_key(|word| word.len()), "crab");
+    assert_eq!(words.min_by_key(|word| word.len()), "ox");
+    assert_eq!(words.sort_by_key(|word| word.len()), Pair::new("ox", "crab"));
+    assert_eq!(words.max_by(|a, b| b.cmp(a)), "crab");
+    assert_eq!(words.min_by(|a, b| b.cmp(a)), "ox");
+    // Equal keys keep the pair as it is
+    assert_eq!(Pair::new("ab", "cd").sort_by_key(|word| word.len()), Pair::new("ab", "cd"));
+}
+
+#[test]
+fn compares_and_hashes_like_a_tuple() {
+    assert!(Pair::new(1, 9) < Pair::new(2, 0));
+    assert!(Pair::new(1, 2) < Pair::new(1, 3));
+    assert_eq!(Pair::new(3, 1).cmp(&Pair::new(3, 1)), std::cmp::Ordering::Equal);
+
+    let mut pairs = vec![Pair::new(2, 1), Pair::new(1, 5), Pair::new(1, 2)];
+    pairs.sort();
+    assert_eq!(pairs, [Pair::new(1, 2), Pair::new(1, 5), Pair::new(2, 1)]);
+
+    let set: HashSet<Pair<u8>> = [Pair::new(1, 2), Pair::new(2, 1), Pair::new(1, 2)].into();
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn converts_from_and_into_tuples() {
+    let pair = Pair::from(("x", "y"));
+    assert_eq!(pair, Pair::new("x", "y"));
+
+    let (x, y): (&str, &str) = pair.into();
+    assert_eq!((x, y), ("x", "y"));
+    assert_eq!(pair.to_string(), "(x, y)");
+}
+
+#[test]
+fn ordered_pairs_keep_the_smaller_member_first() {
+    let ordered = OrderedPair::new(10, 5);
+    assert_eq!((*ordered.low(), *ordered.high()), (5, 10));
+    assert_eq!(ordered, Pair::new(5, 10).sorted());
+    assert_eq!(ordered, OrderedPair::from((5, 10)));
+    assert_eq!(ordered, OrderedPair::from(Pair::new(10, 5)));
+    assert_eq!(Pair::from(ordered), Pair::new(5, 10));
+    assert_eq!(ordered.to_string(), "[5, 10]");
+
+    assert!(ordered.contains(&5));
+    assert!(ordered.contains(&7));
+    assert!(!ordered.contains(&11));
+
+    let set: HashSet<OrderedPair<u8>> = [OrderedPair::new(1, 2), OrderedPair::new(2, 1)].into();
+    assert_eq!(set.len(), 1);
+}
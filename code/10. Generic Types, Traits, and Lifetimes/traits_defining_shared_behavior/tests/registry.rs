@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use traits_defining_shared_behavior::aggregator::Feed;
+use traits_defining_shared_behavior::json::Json;
+use traits_defining_shared_behavior::registry::{Registry, RegistryError};
+use traits_defining_shared_behavior::Summary;
+
+struct Podcast {
+    show: String,
+    episode: u64,
+    title: String,
+    published_at: u64,
+}
+
+impl Summary for Podcast {
+    fn summarize_author(&self) -> String {
+        self.show.clone()
+    }
+
+    fn summarize(&self) -> String {
+        format!("{} #{}: {}", self.show, self.episode, self.title)
+    }
+
+    fn published_at(&self) -> Option<u64> {
+        Some(self.published_at)
+    }
+
+    fn content_type(&self) -> &str {
+        "podcast"
+    }
+}
+
+fn podcast(item: &Json) -> Result<Podcast, String> {
+    Ok(Podcast {
+        show: String::from(item.field_str("show")?),
+        episode: item.field_u64("episode")?,
+        title: String::from(item.field_str("title")?),
+        published_at: item.field_u64("published_at")?,
+    })
+}
+
+fn registry_with_podcasts() -> Registry {
+    let mut registry = Registry::new();
+    registry.register("podcast", podcast).unwrap();
+    registry
+}
+
+fn fixture() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/mixed.json"))
+}
+
+#[test]
+fn builds_the_built_in_types() {
+    let registry = Registry::new();
+    let item = Json::parse(
+        r#"{"type": "tweet", "id": 7, "username": "ferris", "content": "hi", "published_at": 5}"#,
+    )
+    .unwrap();
+
+    let tweet = registry.construct(&item).unwrap();
+    assert_eq!(tweet.content_type(), "tweet");
+    assert_eq!(tweet.summarize_author(), "@ferris");
+    assert_eq!(tweet.published_at(), Some(5));
+    assert_eq!(registry.content_types().collect::<Vec<_>>(), ["article", "tweet"]);
+}
+
+#[test]
+fn types_registered_at_runtime_can_be_loaded() {
+    let mut feed = Feed::load_json(fixture(), &registry_with_podcasts()).unwrap();
+    feed.sort_by_time();
+
+    let types: Vec<&str> = feed.iter().map(|item| item.content_type()).collect();
+    assert_eq!(types, ["podcast", "article", "tweet", "tweet"]);
+    assert_eq!(
+        feed.iter().next().unwrap().summarize(),
+        "Rustacean Station #42: Traits all the way down"
+    );
+}
+
+#[test]
+fn unknown_types_and_bad_items_are_reported_with_their_index() {
+    // Without podcasts registered, the third item can't be read
+    assert!(matches!(
+        Feed::load_json(fixture(), &Registry::new()),
+        Err(RegistryError::UnknownType { index: 2, ref content_type }) if content_type == "podcast"
+    ));
+
+    let registry = registry_with_podcasts();
+    let error = registry
+        .parse(r#"[{"type": "podcast", "show": "Rustacean Station", "title": "?"}]"#)
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "item 0 isn't a valid podcast: expected a non-negative integer 'episode'"
+    );
+
+    assert!(matches!(
+        registry.parse(r#"[{"show": "untagged"}]"#),
+        Err(RegistryError::Untagged { index: 0 })
+    ));
+    assert!(matches!(registry.parse(r#"{"type": "tweet"}"#), Err(RegistryError::NotAList)));
+    assert!(matches!(registry.parse("[{"), Err(RegistryError::Json(_))));
+    assert!(matches!(
+        Feed::load_json("no/such/file.json", &registry),
+        Err(RegistryError::Io(_))
+    ));
+}
+
+#[test]
+fn hostile_json_is_an_error_not_a_crash() {
+    let registry = Registry::new();
+    assert!(matches!(registry.parse(&"[".repeat(200_000)), Err(RegistryError::Json(_))));
+    let nested = format!("{}{}", "[".repeat(Json::MAX_DEPTH), "]".repeat(Json::MAX_DEPTH));
+    assert!(Json::parse(&nested).is_ok());
+
+    let items = registry
+        .parse(
+            r#"[{"type": "tweet", "id": 1, "username": "ferris", "content": "\ud83d\ude00",
+                 "published_at": 5}]"#,
+        )
+        .unwrap();
+    assert_eq!(items[0].field("content").as_deref(), Some("\u{1F600}"));
+    assert!(matches!(registry.parse(r#"["\ud83d"]"#), Err(RegistryError::Json(_))));
+}
+
+#[test]
+fn tweet_flags_must_be_booleans() {
+    let registry = Registry::new();
+    let tweet = |reply: &str| {
+        registry.parse(&format!(
+            r#"[{{"type": "tweet", "id": 1, "username": "ferris", "content": "hi",
+                  "published_at": 5{reply}}}]"#
+        ))
+    };
+
+    assert!(tweet("").is_ok(), "a missing flag is false");
+    assert!(tweet(r#", "reply": true"#).is_ok());
+    assert!(matches!(
+        tweet(r#", "reply": "yes""#),
+        Err(RegistryError::Invalid { index: 0, ref message, .. }) if message.contains("reply")
+    ));
+}
+
+#[test]
+fn types_cannot_be_registered_twice() {
+    let mut registry = registry_with_podcasts();
+
+    assert!(matches!(
+        registry.register("podcast", podcast),
+        Err(RegistryError::AlreadyRegistered(ref name)) if name == "podcast"
+    ));
+    assert_eq!(registry.content_types().collect::<Vec<_>>(), ["article", "podcast", "tweet"]);
+    assert_eq!(Registry::empty().content_types().count(), 0);
+}
@@ -0,0 +1,68 @@
+use traits_defining_shared_behavior::summarizer::{extract, score_sentences, sentences};
+use traits_defining_shared_behavior::{NewsArticle, Summary};
+
+const STORY: &str = "The Penguins won the Stanley Cup on Sunday. \
+    Fans waited outside the arena for hours. \
+    The Penguins beat Detroit, and the Cup returns to Pittsburgh for the Penguins' parade. \
+    It rained.";
+
+#[test]
+fn splits_sentences_but_not_abbreviations_or_initials() {
+    assert_eq!(
+        sentences("Mr. Lemieux scored. J. R. Smith didn't! Did they win? \"Yes.\" it ends"),
+        ["Mr. Lemieux scored.", "J. R. Smith didn't!", "Did they win?", "\"Yes.\" it ends"]
+    );
+    assert_eq!(sentences("  "), Vec::<&str>::new());
+    assert_eq!(sentences("No punctuation"), ["No punctuation"]);
+}
+
+#[test]
+fn sentences_about_the_subject_score_highest() {
+    let scored = score_sentences(STORY);
+
+    assert_eq!(scored.len(), 4);
+    assert!(scored[2].1 > scored[1].1);
+    assert!(scored[0].1 > scored[3].1);
+    // Words that appear once score the least, and stop words don't count at all
+    assert_eq!(scored[3], ("It rained.", 1.0 / 3.0));
+    assert_eq!(score_sentences("It is."), [("It is.", 0.0)]);
+}
+
+#[test]
+fn extracts_the_best_sentences_in_their_original_order() {
+    assert_eq!(
+        extract(STORY, 2, 500),
+        "The Penguins won the Stanley Cup on Sunday. \
+         The Penguins beat Detroit, and the Cup returns to Pittsburgh for the Penguins' parade."
+    );
+    assert_eq!(extract(STORY, 0, 500), "");
+    assert_eq!(extract("", 3, 500), "");
+}
+
+#[test]
+fn extraction_respects_the_length_budget() {
+    // The best sentence doesn't fit, so the next best that does is taken
+    assert_eq!(extract(STORY, 1, 50), "The Penguins won the Stanley Cup on Sunday.");
+    assert_eq!(extract(STORY, 3, 5), "");
+
+    for max_chars in 0..200 {
+        assert!(extract(STORY, 4, max_chars).chars().count() <= max_chars);
+    }
+}
+
+#[test]
+fn article_summaries_quote_the_key_sentences() {
+    let article = NewsArticle {
+        headline: String::from("Cup!"),
+        location: String::from("Pittsburgh"),
+        author: String::from("Iceburgh"),
+        content: String::from(STORY),
+        published_at: 0,
+    };
+
+    assert_eq!(
+        article.summarize_within(80),
+        "Cup!, by Iceburgh (Pittsburgh): The Penguins won the Stanley Cup on Sunday."
+    );
+    assert!(article.summarize().chars().count() <= NewsArticle::SUMMARY_LENGTH);
+}
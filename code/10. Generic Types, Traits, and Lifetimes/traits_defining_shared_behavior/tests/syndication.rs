@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use traits_defining_shared_behavior::syndication::{self, Channel, Entry, FeedError};
+use traits_defining_shared_behavior::{NewsArticle, Summary, Tweet};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+fn channel() -> Channel {
+    Channel {
+        title: String::from("Penguins & friends"),
+        link: String::from("https://example.com/news/"),
+        description: String::from("Hockey <news> and chatter"),
+    }
+}
+
+fn entries() -> Vec<Entry> {
+    vec![
+        Entry::from(NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::from(
+                "The Pittsburgh Penguins once again are the best hockey team in the NHL.",
+            ),
+            published_at: 1_722_873_600,
+        }),
+        Entry::from(Tweet {
+            id: 2,
+            username: String::from("horse_ebooks"),
+            content: String::from("of course, as you probably already know, people"),
+            reply: false,
+            retweet: true,
+            parent: Some(1),
+            quoted: None,
+            published_at: 1_722_870_000,
+        }),
+        Entry::from(Tweet {
+            id: 3,
+            username: String::from("rustlang"),
+            content: String::from("Fearless <concurrency> & \"zero-cost\" abstractions"),
+            reply: true,
+            retweet: false,
+            parent: Some(1),
+            quoted: Some(2),
+            published_at: 1_722_866_400,
+        }),
+    ]
+}
+
+#[test]
+fn rss_export_matches_the_fixture_and_reads_back() {
+    let rss = syndication::to_rss(&channel(), &entries());
+
+    assert_eq!(rss, fs::read_to_string(fixture("feed.rss")).unwrap());
+    assert_eq!(syndication::load(fixture("feed.rss")).unwrap(), entries());
+}
+
+#[test]
+fn atom_export_matches_the_fixture_and_reads_back() {
+    let atom = syndication::to_atom(&channel(), &entries());
+
+    assert_eq!(atom, fs::read_to_string(fixture("feed.atom")).unwrap());
+    assert_eq!(syndication::load(fixture("feed.atom")).unwrap(), entries());
+}
+
+#[test]
+fn reads_feeds_written_elsewhere() {
+    let rss = syndication::load(fixture("external.rss")).unwrap();
+    let atom = syndication::load(fixture("external.atom")).unwrap();
+
+    for entries in [&rss, &atom] {
+        assert_eq!(entries.len(), 2);
+        let Entry::Article(article) = &entries[0] else {
+            panic!("items without a type are read as articles");
+        };
+        assert_eq!(article.headline, "Ferris & the <crab> club");
+        assert_eq!(article.author, "Ferris");
+        assert_eq!(article.content, "Rustaceans meet on Tuesdays.");
+        // 2024-08-05 12:00 at UTC-4
+        assert_eq!(article.published_at(), Some(1_722_873_600));
+    }
+    assert_eq!(rss, atom);
+}
+
+#[test]
+fn parsed_entries_are_summaries() {
+    let entries = syndication::load(fixture("feed.atom")).unwrap();
+    let summaries: Vec<&dyn Summary> = entries.iter().map(|entry| entry as &dyn Summary).collect();
+
+    assert_eq!(summaries[1].summarize(), "(Read more from @horse_ebooks...)");
+    assert!(summaries[0].summarize().starts_with("Penguins win the Stanley Cup"));
+}
+
+#[test]
+fn rejects_broken_documents() {
+    assert!(matches!(
+        syndication::parse("<rss><channel></rss>"),
+        Err(FeedError::Xml { .. })
+    ));
+    assert!(matches!(
+        syndication::parse("<html></html>"),
+        Err(FeedError::Format(_))
+    ));
+    assert!(matches!(
+        syndication::parse(
+            "<rss><channel><item><pubDate>yesterday</pubDate></item></channel></rss>"
+        ),
+        Err(FeedError::Format(_))
+    ));
+    assert!(matches!(
+        syndication::load(fixture("missing.rss")),
+        Err(FeedError::Io(_))
+    ));
+}
+
+#[test]
+fn dates_too_far_ahead_are_rejected() {
+    let rss = |date: &str| {
+        syndication::parse(&format!(
+            "<rss><channel><item><pubDate>{date}</pubDate></item></channel></rss>"
+        ))
+    };
+    let atom = |date: &str| {
+        syndication::parse(&format!(
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\"><entry><published>{date}</published>\
+             </entry></feed>"
+        ))
+    };
+
+    assert!(matches!(rss("05 Aug 18446744073709551615 16:00 GMT"), Err(FeedError::Format(_))));
+    assert!(matches!(rss("05 Aug 100000000000000 16:00 -0400"), Err(FeedError::Format(_))));
+    assert!(matches!(atom("100000000000000-08-05T16:00:00Z"), Err(FeedError::Format(_))));
+    assert!(matches!(atom("9999-12-31T23:59:59Z"), Ok(entries) if entries.len() == 1));
+}
+
+#[test]
+fn deeply_nested_documents_are_errors_not_crashes() {
+    let nested = |depth: usize| format!("{}{}", "<a>".repeat(depth), "</a>".repeat(depth));
+
+    // Well within the limit, but not a feed
+    assert!(matches!(syndication::parse(&nested(10)), Err(FeedError::Format(_))));
+    assert!(matches!(syndication::parse(&nested(200_000)), Err(FeedError::Xml { .. })));
+}
@@ -0,0 +1,154 @@
+use traits_defining_shared_behavior::templates::{Format, Template, TemplateError, Templates};
+use traits_defining_shared_behavior::{NewsArticle, Summary, Tweet};
+
+fn article() -> NewsArticle {
+    NewsArticle {
+        headline: String::from("Penguins <3 the Cup"),
+        location: String::from("Pittsburgh"),
+        author: String::from("Iceburgh"),
+        content: String::from("The Penguins are the best hockey team in the NHL."),
+        published_at: 1_722_873_600,
+    }
+}
+
+fn tweet() -> Tweet {
+    Tweet {
+        id: 1,
+        username: String::from("horse_ebooks"),
+        content: String::from("of course"),
+        reply: false,
+        retweet: false,
+        parent: None,
+        quoted: None,
+        published_at: 1_722_870_000,
+    }
+}
+
+#[test]
+fn renders_placeholders_in_every_format() {
+    let template = Template::parse("{headline:strong}, by {author:em} ({location})").unwrap();
+    let article = article();
+
+    assert_eq!(
+        template.render(&article, Format::PlainText).unwrap(),
+        "Penguins <3 the Cup, by Iceburgh (Pittsburgh)"
+    );
+    assert_eq!(
+        template.render(&article, Format::Markdown).unwrap(),
+        "**Penguins \\<3 the Cup**, by *Iceburgh* (Pittsburgh)"
+    );
+    assert_eq!(
+        template.render(&article, Format::Html).unwrap(),
+        "<strong>Penguins &lt;3 the Cup</strong>, by <em>Iceburgh</em> (Pittsburgh)"
+    );
+}
+
+#[test]
+fn every_item_knows_author_type_summary_and_date() {
+    let template = Template::parse("{type} by {author} on {date}: {summary}").unwrap();
+
+    assert_eq!(
+        template.render(&tweet(), Format::PlainText).unwrap(),
+        "tweet by @horse_ebooks on 2024-08-05 15:00: (Read more from @horse_ebooks...)"
+    );
+}
+
+#[test]
+fn missing_placeholders_are_errors() {
+    let template = Template::parse("{headline} ({location})").unwrap();
+
+    assert_eq!(
+        template.render(&tweet(), Format::PlainText),
+        Err(TemplateError::MissingPlaceholder {
+            placeholder: String::from("headline"),
+            content_type: String::from("tweet"),
+        })
+    );
+}
+
+#[test]
+fn malformed_templates_are_rejected() {
+    assert_eq!(
+        Template::parse("{{literal}} {author}").unwrap().placeholders().collect::<Vec<_>>(),
+        ["author"]
+    );
+    assert_eq!(
+        Template::parse("{{literal}}").unwrap().render(&tweet(), Format::PlainText).unwrap(),
+        "{literal}"
+    );
+
+    for source in ["{author", "author}", "{}", "{author:loud}"] {
+        assert!(
+            matches!(Template::parse(source), Err(TemplateError::Syntax { .. })),
+            "{source} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn templates_fall_back_through_locales_and_types() {
+    let templates = Templates::default();
+
+    assert_eq!(
+        templates.render(&article(), "fr-CA", Format::PlainText).unwrap(),
+        "Penguins <3 the Cup, par Iceburgh (Pittsburgh)"
+    );
+    assert_eq!(
+        templates.render(&tweet(), "fr", Format::PlainText).unwrap(),
+        "@horse_ebooks: of course"
+    );
+    assert_eq!(
+        templates.render(&tweet(), "de", Format::PlainText).unwrap(),
+        "@horse_ebooks: of course"
+    );
+}
+
+#[test]
+fn the_default_item_template_matches_summarize() {
+    struct Anonymous;
+
+    impl Summary for Anonymous {
+        fn summarize_author(&self) -> String {
+            String::from("someone")
+        }
+    }
+
+    let templates = Templates::default();
+    assert_eq!(
+        templates.render(&Anonymous, "en", Format::PlainText).unwrap(),
+        Anonymous.summarize()
+    );
+}
+
+#[test]
+fn a_type_without_any_template_is_an_error() {
+    let mut templates = Templates::new("en");
+    templates.add("article", "en", "{headline}").unwrap();
+
+    assert_eq!(
+        templates.render(&tweet(), "en", Format::Html),
+        Err(TemplateError::NoTemplate {
+            content_type: String::from("tweet"),
+            locale: String::from("en"),
+        })
+    );
+}
+
+#[test]
+fn the_default_summary_is_the_english_item_template() {
+    struct Note;
+
+    impl Summary for Note {
+        fn summarize_author(&self) -> String {
+            String::from("Ferris")
+        }
+    }
+
+    let templates = Templates::default();
+    assert_eq!(Note.summarize(), "(Read more from Ferris...)");
+    assert_eq!(templates.render(&Note, "en", Format::PlainText).unwrap(), Note.summarize());
+    assert_eq!(
+        templates.render(&Note, "fr", Format::PlainText).unwrap(),
+        "(Lire la suite de Ferris...)"
+    );
+}
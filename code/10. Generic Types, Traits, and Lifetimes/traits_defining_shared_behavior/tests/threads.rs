@@ -0,0 +1,163 @@
+use traits_defining_shared_behavior::threads::{ThreadError, Timeline};
+use traits_defining_shared_behavior::{Summary, Tweet};
+
+fn tweet(id: u64, username: &str, content: &str) -> Tweet {
+    Tweet {
+        id,
+        username: String::from(username),
+        content: String::from(content),
+        reply: false,
+        retweet: false,
+        parent: None,
+        quoted: None,
+        published_at: 1_000 + id,
+    }
+}
+
+fn reply(id: u64, username: &str, parent: u64) -> Tweet {
+    Tweet {
+        reply: true,
+        parent: Some(parent),
+        ..tweet(id, username, "Agreed!")
+    }
+}
+
+fn retweet(id: u64, username: &str, parent: u64) -> Tweet {
+    Tweet {
+        retweet: true,
+        parent: Some(parent),
+        ..tweet(id, username, "")
+    }
+}
+
+fn ids<'a>(tweets: impl IntoIterator<Item = &'a Tweet>) -> Vec<u64> {
+    tweets.into_iter().map(|tweet| tweet.id).collect()
+}
+
+/// 1 <- 2 <- 4, 1 <- 3, with retweets 5 -> 1 and 6 -> 5, and 7 quoting 1.
+fn timeline() -> Timeline {
+    Timeline::new([
+        tweet(1, "rustlang", "Rust 1.80 is out, with LazyCell and LazyLock"),
+        reply(2, "ferris", 1),
+        reply(3, "corro", 1),
+        reply(4, "rustlang", 2),
+        retweet(5, "ferris", 1),
+        retweet(6, "corro", 5),
+        Tweet {
+            quoted: Some(1),
+            ..tweet(7, "corro", "Finally!")
+        },
+    ])
+}
+
+#[test]
+fn rebuilds_threads_in_reading_order() {
+    let timeline = timeline();
+    let thread = timeline.thread(4).unwrap();
+
+    assert_eq!(thread.root().id, 1);
+    let replies: Vec<(usize, u64)> =
+        thread.replies().iter().map(|(depth, tweet)| (*depth, tweet.id)).collect();
+    assert_eq!(replies, [(1, 2), (2, 4), (1, 3)]);
+    assert_eq!(thread.participants(), ["rustlang", "ferris", "corro"]);
+    assert_eq!(ids(timeline.replies_to(1)), [2, 3]);
+    assert_eq!(ids(timeline.conversation(4).unwrap()), [1, 2, 4]);
+}
+
+#[test]
+fn threads_summarize_through_the_summary_trait() {
+    let timeline = timeline();
+    let thread = timeline.thread(1).unwrap();
+    let summary: &dyn Summary = &thread;
+
+    assert_eq!(
+        summary.summarize(),
+        "@rustlang: Rust 1.80 is out, with LazyCell and LazyLock (3 replies from 2 others)"
+    );
+    assert_eq!(summary.content_type(), "thread");
+    // The latest reply
+    assert_eq!(summary.published_at(), Some(1_004));
+    assert_eq!(timeline.thread(7).unwrap().summarize(), "@corro: Finally!");
+}
+
+#[test]
+fn follows_retweet_chains_both_ways() {
+    let timeline = timeline();
+
+    assert_eq!(ids(timeline.retweet_chain(6).unwrap()), [6, 5, 1]);
+    assert_eq!(ids(timeline.retweet_chain(1).unwrap()), [1]);
+    assert_eq!(ids(timeline.retweets_of(1)), [5, 6]);
+    assert_eq!(ids(timeline.quotes_of(1)), [7]);
+}
+
+#[test]
+fn lists_every_thread_once() {
+    let timeline = timeline();
+    let threads = timeline.threads();
+
+    let roots: Vec<u64> = threads.iter().map(|thread| thread.root().id).collect();
+    assert_eq!(roots, [1, 7]);
+    let tweets: usize = threads.iter().map(|thread| thread.len()).sum();
+    assert_eq!(tweets, 5, "every tweet but the retweets");
+}
+
+#[test]
+fn reports_missing_parents() {
+    let timeline = Timeline::new([
+        reply(2, "ferris", 1),
+        reply(3, "corro", 2),
+        retweet(4, "corro", 9),
+    ]);
+
+    assert_eq!(
+        timeline.conversation(3),
+        Err(ThreadError::MissingParent { tweet: 2, parent: 1 })
+    );
+    assert_eq!(
+        timeline.retweet_chain(4),
+        Err(ThreadError::MissingParent { tweet: 4, parent: 9 })
+    );
+    assert_eq!(timeline.conversation(42), Err(ThreadError::UnknownTweet(42)));
+
+    // The thread still starts where the timeline does
+    let thread = timeline.thread(3).unwrap();
+    assert_eq!(thread.root().id, 2);
+    assert_eq!(thread.missing_parent(), Some(1));
+    assert_eq!(ids(thread.tweets()), [2, 3]);
+}
+
+#[test]
+fn survives_cycles() {
+    // 1 replies to 3, which replies to 2, which replies to 1; 4 replies into the loop
+    let timeline = Timeline::new([
+        reply(1, "a", 3),
+        reply(2, "b", 1),
+        reply(3, "c", 2),
+        reply(4, "d", 2),
+        retweet(5, "e", 6),
+        retweet(6, "f", 5),
+    ]);
+
+    assert!(matches!(timeline.conversation(4), Err(ThreadError::Cycle(ids)) if ids.len() == 3));
+    assert!(matches!(timeline.retweet_chain(5), Err(ThreadError::Cycle(_))));
+
+    let thread = timeline.thread(4).unwrap();
+    assert!(thread.is_cyclic());
+    assert_eq!(thread.root().id, 1);
+    assert_eq!(ids(thread.tweets()), [1, 2, 3, 4]);
+    assert_eq!(timeline.threads().len(), 1);
+    assert_eq!(ids(timeline.retweets_of(5)), [6]);
+}
+
+#[test]
+fn handles_very_long_threads() {
+    const LENGTH: u64 = 300_000;
+    let replies = (2..=LENGTH).map(|id| reply(id, "ferris", id - 1));
+    let timeline = Timeline::new(std::iter::once(tweet(1, "rustlang", "Start")).chain(replies));
+
+    let thread = timeline.thread(LENGTH).unwrap();
+    assert_eq!(thread.root().id, 1);
+    assert_eq!(thread.len(), LENGTH as usize);
+    assert_eq!(thread.replies().last().map(|(depth, _)| *depth), Some(LENGTH as usize - 1));
+    assert_eq!(timeline.conversation(LENGTH).unwrap().len(), LENGTH as usize);
+}
@@ -0,0 +1,185 @@
+//! Rectangles with a place on a plane, and more ways of comparing sizes.
+//!
+//! Coordinates grow right and down, like on a screen: a rectangle's origin is its top left corner.
+//! Edges are half-open, so a rectangle at x = 0 with width 10 covers x = 0 to 9, and two
+//! rectangles that only touch don't overlap.
+
+use crate::Rectangle;
+
+impl Rectangle {
+    pub fn perimeter(&self) -> u64 {
+        2 * (u64::from(self.width) + u64::from(self.height))
+    }
+
+    /// Returns the rectangle turned a quarter, with width and height exchanged.
+    pub fn rotated(&self) -> Rectangle {
+        Rectangle {
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// Like `can_hold`, but `other` may also be turned a quarter to fit.
+    pub fn can_hold_rotated(&self, other: &Rectangle) -> bool {
+        self.can_hold(other) || self.can_hold(&other.rotated())
+    }
+
+    /// Like `can_hold`, but `other` may be as wide or as high as `self`, so a rectangle fits
+    /// itself.
+    pub fn can_fit(&self, other: &Rectangle) -> bool {
+        self.width >= other.width && self.height >= other.height
+    }
+
+    /// Like `can_fit`, but `other` may also be turned a quarter to fit.
+    pub fn can_fit_rotated(&self, other: &Rectangle) -> bool {
+        self.can_fit(other) || self.can_fit(&other.rotated())
+    }
+
+    /// Places the rectangle with its top left corner at `origin`.
+    pub fn at(self, origin: Point) -> PositionedRectangle {
+        PositionedRectangle { origin, size: self }
+    }
+}
+
+/// A point on the plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A `Rectangle` placed on the plane: where its top left corner is, and how big it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PositionedRectangle {
+    pub origin: Point,
+    pub size: Rectangle,
+}
+
+/// Places a rectangle at the origin.
+impl From<Rectangle> for PositionedRectangle {
+    fn from(size: Rectangle) -> Self {
+        size.at(Point::default())
+    }
+}
+
+impl PositionedRectangle {
+    pub fn new(x: i64, y: i64, width: u32, height: u32) -> Self {
+        Self {
+            origin: Point::new(x, y),
+            size: Rectangle { width, height },
+        }
+    }
+
+    /// Creates the rectangle between two corners, in any order.
+    ///
+    /// A `Rectangle` is at most `u32::MAX` wide and high. Corners further apart than that give a
+    /// rectangle that starts at the top left corner and is cut off at that size, so it doesn't
+    /// reach the bottom right one; `union` and `bounding_box` are cut off the same way.
+    pub fn from_corners(a: Point, b: Point) -> Self {
+        let size = |from: i64, to: i64| u32::try_from(from.abs_diff(to)).unwrap_or(u32::MAX);
+        Self::new(a.x.min(b.x), a.y.min(b.y), size(a.x, b.x), size(a.y, b.y))
+    }
+
+    pub fn left(&self) -> i64 {
+        self.origin.x
+    }
+
+    pub fn top(&self) -> i64 {
+        self.origin.y
+    }
+
+    /// The x just past the right edge.
+    ///
+    /// Saturates at `i64::MAX` for a rectangle reaching past the edge of the plane, which then
+    /// behaves as if cut off there.
+    pub fn right(&self) -> i64 {
+        self.origin.x.saturating_add(i64::from(self.size.width))
+    }
+
+    /// The y just past the bottom edge, saturating like `right`.
+    pub fn bottom(&self) -> i64 {
+        self.origin.y.saturating_add(i64::from(self.size.height))
+    }
+
+    pub fn area(&self) -> u64 {
+        self.size.area()
+    }
+
+    pub fn perimeter(&self) -> u64 {
+        self.size.perimeter()
+    }
+
+    /// Whether the rectangle covers no area at all.
+    pub fn is_empty(&self) -> bool {
+        self.size.width == 0 || self.size.height == 0
+    }
+
+    /// Whether `point` lies in the rectangle. Points on the right and bottom edges don't.
+    pub fn contains_point(&self, point: Point) -> bool {
+        (self.left()..self.right()).contains(&point.x)
+            && (self.top()..self.bottom()).contains(&point.y)
+    }
+
+    /// Whether `other` lies entirely within the rectangle; it may share edges with it.
+    pub fn contains(&self, other: &PositionedRectangle) -> bool {
+        self.left() <= other.left()
+            && self.top() <= other.top()
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Whether the two rectangles share some area. Rectangles that only touch don't.
+    pub fn overlaps(&self, other: &PositionedRectangle) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the area the two rectangles share, if they share any.
+    pub fn intersection(&self, other: &PositionedRectangle) -> Option<PositionedRectangle> {
+        let left = self.left().max(other.left());
+        let top = self.top().max(other.top());
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+
+        if left < right && top < bottom {
+            Some(Self::from_corners(Point::new(left, top), Point::new(right, bottom)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest rectangle covering both, their bounding box, cut off as in
+    /// `from_corners` if it would be larger than a `Rectangle` can be.
+    pub fn union(&self, other: &PositionedRectangle) -> PositionedRectangle {
+        Self::from_corners(
+            Point::new(self.left().min(other.left()), self.top().min(other.top())),
+            Point::new(self.right().max(other.right()), self.bottom().max(other.bottom())),
+        )
+    }
+
+    /// Returns the bounding box of all the rectangles, or `None` if there are none.
+    pub fn bounding_box<'a>(
+        rectangles: impl IntoIterator<Item = &'a PositionedRectangle>,
+    ) -> Option<PositionedRectangle> {
+        rectangles
+            .into_iter()
+            .copied()
+            .reduce(|bounds, rectangle| bounds.union(&rectangle))
+    }
+
+    /// Returns the rectangle moved by `dx` and `dy`.
+    ///
+    /// Like `right` and `bottom`, the corner saturates at the edge of the plane instead of
+    /// overflowing, so a rectangle moved too far stops there.
+    pub fn translated(&self, dx: i64, dy: i64) -> PositionedRectangle {
+        Self {
+            origin: Point::new(self.origin.x.saturating_add(dx), self.origin.y.saturating_add(dy)),
+            size: self.size,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+pub mod geometry;
+pub mod packing;
+pub mod shapes;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    // Widened to u64 so that large rectangles don't overflow
+    pub fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    pub fn width(&self) -> bool {
+        self.width > 0
+    }
+
+    // New addition for the lasting blocks of code
+    /* Defines the `can_hold` method within the `impl Rectangle` block.
+     * This method takes an immutable borrow of another `Rectangle` as a parameter, indicated by
+     * usage such as `rect1.can_hold(&rect2)`. It only needs to read the properties of the borrowed
+     * `Rectangle`, hence an immutable borrow is sufficient. The method returns a Boolean value,
+     * true if `self` can completely contain the other `Rectangle` based on comparing their width
+     * and height.
+     */
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width > other.width && self.height > other.height
+    }
+
+    /* Creates a square `Rectangle` where the width and height are equal.
+     * This is an associated function, not requiring an instance of `Rectangle`.
+     * Usage: `Rectangle::square(size)` returns a new `Rectangle` instance with specified dimensions.
+     */
+    // Just like String::from to make the things easier to create.
+    pub fn square(size: u32) -> Self {
+        Self {
+            width: size,
+            height: size,
+        }
+    }
+}
@@ -1,43 +1,9 @@
-// It's used around the code.
-#[derive(Debug)]
-struct Rectangle {
-    width: u32,
-    height: u32,
-}
-
-impl Rectangle {
-    fn area(&self) -> u32 {
-        self.width * self.height
-    }
-
-    fn width(&self) -> bool {
-        self.width > 0
-    }
-
-    // New addition for the lasting blocks of code
-    /* Defines the `can_hold` method within the `impl Rectangle` block.
-     * This method takes an immutable borrow of another `Rectangle` as a parameter, indicated by
-     * usage such as `rect1.can_hold(&rect2)`. It only needs to read the properties of the borrowed
-     * `Rectangle`, hence an immutable borrow is sufficient. The method returns a Boolean value,
-     * true if `self` can completely contain the other `Rectangle` based on comparing their width
-     * and height.
-     */
-    fn can_hold(&self, other: &Rectangle) -> bool {
-        self.width > other.width && self.height > other.height
-    }
-
-    /* Creates a square `Rectangle` where the width and height are equal.
-     * This is an associated function, not requiring an instance of `Rectangle`.
-     * Usage: `Rectangle::square(size)` returns a new `Rectangle` instance with specified dimensions.
-     */
-    // Just like String::from to make the things easier to create.
-    fn square(size: u32) -> Self {
-        Self {
-            width: size,
-            height: size,
-        }
-    }
-}
+// It's used around the code. `Rectangle` and its methods moved to lib.rs, next to the geometry
+// built on them.
+use method_syntax::geometry::{Point, PositionedRectangle};
+use method_syntax::packing::{self, Heuristic, Options};
+use method_syntax::shapes::{Circle, Polygon, Scene, Triangle, Vertex};
+use method_syntax::Rectangle;
 
 fn main() {
     defining_methods();
@@ -47,6 +13,12 @@ fn main() {
     methods_with_more_parameters();
 
     example_for_making_a_square_using_associated_function();
+
+    example_of_rectangles_on_a_plane();
+
+    example_of_packing_rectangles();
+
+    example_of_a_scene_of_shapes();
 }
 
 // Let’s change the area function that has a Rectangle instance as a parameter and instead make an
@@ -122,4 +94,80 @@ fn example_for_making_a_square_using_associated_function() {
     let sq = Rectangle::square(3);
 
     println!("Square {:?}", sq);
-}
\ No newline at end of file
+}
+
+/* Rectangles with an origin can be compared by where they are, not only by size. The geometry
+ * module adds the positioned rectangles, and `can_hold` variants that allow turning the other
+ * rectangle or an exact fit.
+ */
+fn example_of_rectangles_on_a_plane() {
+    let rect1 = Rectangle {
+        width: 30,
+        height: 50,
+    };
+    let wide = Rectangle {
+        width: 45,
+        height: 20,
+    };
+    println!("Can rect1 hold wide? {}", rect1.can_hold(&wide));
+    println!("Can rect1 hold wide turned? {}", rect1.can_hold_rotated(&wide));
+    println!("Does rect1 fit itself? {}", rect1.can_fit(&rect1));
+
+    let window = rect1.at(Point::new(0, 0));
+    let dialog = PositionedRectangle::new(20, 40, 30, 30);
+    println!("Perimeter of the window: {}", window.perimeter());
+    println!("Overlap: {:?}", window.intersection(&dialog));
+    println!("Bounding box: {:?}", window.union(&dialog));
+    let point = Point::new(25, 45);
+    println!(
+        "Is (25, 45) in both? {}",
+        window.contains_point(point) && dialog.contains_point(point)
+    );
+}
+
+/* `can_hold` answers whether one rectangle fits in another. The packing module asks it over and
+ * over to place many rectangles in containers, and reports how much of them gets used.
+ */
+fn example_of_packing_rectangles() {
+    let sheet = Rectangle {
+        width: 100,
+        height: 60,
+    };
+    let parts: Vec<Rectangle> = [(50, 30), (40, 40), (60, 20), (30, 30), (20, 50), (25, 10)]
+        .iter()
+        .map(|&(width, height)| Rectangle { width, height })
+        .collect();
+
+    for heuristic in [Heuristic::Shelf, Heuristic::Guillotine, Heuristic::MaxRects] {
+        let options = Options {
+            heuristic,
+            ..Options::default()
+        };
+        println!("{heuristic:?}:\n{}", packing::pack(sheet, &parts, options));
+    }
+}
+
+/* `area` was a method of `Rectangle` alone. The `Shape` trait gives circles, triangles and
+ * polygons the same methods, so a scene can hold them all as `Box<dyn Shape>`, sum them up and
+ * draw them as SVG.
+ */
+fn example_of_a_scene_of_shapes() {
+    let mut scene = Scene::new();
+    scene.add(PositionedRectangle::new(0, 0, 30, 50));
+    scene.add(Circle::new(Vertex::new(50.0, 25.0), 15.0));
+    scene.add(Triangle::new(
+        Vertex::new(70.0, 50.0),
+        Vertex::new(100.0, 50.0),
+        Vertex::new(85.0, 20.0),
+    ));
+    match Polygon::regular(Vertex::new(120.0, 25.0), 15.0, 6) {
+        Ok(hexagon) => scene.add(hexagon),
+        Err(error) => println!("No hexagon: {error}"),
+    }
+
+    for shape in scene.iter() {
+        println!("A {} with an area of {:.1}", shape.kind(), shape.area());
+    }
+    println!("{}", scene.statistics());
+    print!("{}", scene.to_svg(5.0));
+}
@@ -0,0 +1,418 @@
+//! Placing rectangles into containers without overlaps, like cutting parts from sheets.
+//!
+//! Packing well is hard, so this uses heuristics, each trading speed for how tightly it packs:
+//!
+//! * `Heuristic::Shelf` lines items up on horizontal shelves, each as high as its first item.
+//! * `Heuristic::Guillotine` keeps the free space as rectangles, cutting the one an item goes
+//!   into in two, edge to edge.
+//! * `Heuristic::MaxRects` keeps every largest free rectangle, even overlapping ones, and picks
+//!   the one an item fits best.
+//!
+//! Items are packed largest first. Whether an item fits a free space is always decided by
+//! `Rectangle::can_fit`, the `can_hold` that accepts an exact fit, or `can_fit_rotated` when items
+//! may be turned. When a container is full, another is opened, up to `Options::max_containers`.
+
+use std::fmt;
+
+use crate::geometry::{Point, PositionedRectangle};
+use crate::Rectangle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Heuristic {
+    Shelf,
+    Guillotine,
+    #[default]
+    MaxRects,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    pub heuristic: Heuristic,
+    /// Whether items may be turned a quarter to fit.
+    pub allow_rotation: bool,
+    /// How many containers may be used; `None` for as many as it takes.
+    pub max_containers: Option<usize>,
+}
+
+impl Default for Options {
+    /// MaxRects, with rotation, in as many containers as it takes.
+    fn default() -> Self {
+        Self {
+            heuristic: Heuristic::MaxRects,
+            allow_rotation: true,
+            max_containers: None,
+        }
+    }
+}
+
+/// Where one item went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    /// The item's index in the list given to `pack`.
+    pub item: usize,
+    /// Where the item is in its container, whose top left corner is (0, 0).
+    pub position: PositionedRectangle,
+    /// Whether the item was turned a quarter.
+    pub rotated: bool,
+}
+
+/// One container and what was put in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Container {
+    pub size: Rectangle,
+    pub placements: Vec<Placement>,
+}
+
+impl Container {
+    /// The area covered by items.
+    pub fn used_area(&self) -> u64 {
+        self.placements
+            .iter()
+            .map(|placement| placement.position.area())
+            .sum()
+    }
+
+    /// The share of the container covered by items, from 0 to 1.
+    pub fn utilization(&self) -> f64 {
+        ratio(self.used_area(), self.size.area())
+    }
+}
+
+/// The result of `pack`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Packing {
+    /// The containers used, in the order they were opened.
+    pub containers: Vec<Container>,
+    /// The indexes of the items that couldn't be placed, because they're larger than a container
+    /// or the containers ran out.
+    pub unplaced: Vec<usize>,
+}
+
+impl Packing {
+    /// The number of items placed, in all containers.
+    pub fn placed(&self) -> usize {
+        self.containers
+            .iter()
+            .map(|container| container.placements.len())
+            .sum()
+    }
+
+    /// The share of all used containers covered by items, from 0 to 1.
+    pub fn utilization(&self) -> f64 {
+        let used = self.containers.iter().map(Container::used_area).sum();
+        let total = self.containers.iter().map(|container| container.size.area()).sum();
+        ratio(used, total)
+    }
+}
+
+/// A report of how full each container is.
+impl fmt::Display for Packing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, container) in self.containers.iter().enumerate() {
+            writeln!(
+                f,
+                "Container {}: {} item(s), {:.1}% used",
+                index + 1,
+                container.placements.len(),
+                container.utilization() * 100.0
+            )?;
+        }
+        write!(
+            f,
+            "Overall: {} item(s) in {} container(s), {:.1}% used",
+            self.placed(),
+            self.containers.len(),
+            self.utilization() * 100.0
+        )?;
+        if !self.unplaced.is_empty() {
+            write!(f, "; {} item(s) didn't fit", self.unplaced.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Packs `items` into containers of size `container`.
+///
+/// # Arguments
+///
+/// * `container` - The size of every container.
+/// * `items` - The rectangles to place; placements refer to them by index.
+/// * `options` - The heuristic, whether items may turn and how many containers there are.
+///
+/// # Returns
+///
+/// * The containers used with their placements, and the items left over.
+pub fn pack(container: Rectangle, items: &[Rectangle], options: Options) -> Packing {
+    // Largest first: by longer side, then by area
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by_key(|&index| {
+        let item = &items[index];
+        std::cmp::Reverse((item.width.max(item.height), item.area()))
+    });
+
+    let mut spaces: Vec<FreeSpace> = Vec::new();
+    let mut containers: Vec<Container> = Vec::new();
+    let mut unplaced = Vec::new();
+
+    for index in order {
+        let item = items[index];
+        let fits_empty = if options.allow_rotation {
+            container.can_fit_rotated(&item)
+        } else {
+            container.can_fit(&item)
+        };
+        if !fits_empty {
+            unplaced.push(index);
+            continue;
+        }
+
+        let mut placed = spaces.iter_mut().enumerate().find_map(|(open, space)| {
+            space
+                .insert(item, options.allow_rotation)
+                .map(|placement| (open, placement))
+        });
+
+        let can_open = options
+            .max_containers
+            .is_none_or(|max| containers.len() < max);
+        if placed.is_none() && can_open {
+            let mut space = FreeSpace::new(container, options.heuristic);
+            placed = space
+                .insert(item, options.allow_rotation)
+                .map(|placement| (spaces.len(), placement));
+            spaces.push(space);
+            containers.push(Container {
+                size: container,
+                placements: Vec::new(),
+            });
+        }
+
+        match placed {
+            Some((open, (position, rotated))) => containers[open].placements.push(Placement {
+                item: index,
+                position,
+                rotated,
+            }),
+            None => unplaced.push(index),
+        }
+    }
+
+    unplaced.sort_unstable();
+    Packing {
+        containers,
+        unplaced,
+    }
+}
+
+fn ratio(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+/// The orientations `item` may be placed in, upright first.
+fn orientations(item: Rectangle, allow_rotation: bool) -> Vec<(Rectangle, bool)> {
+    let rotated = item.rotated();
+    if allow_rotation && rotated != item {
+        vec![(item, false), (rotated, true)]
+    } else {
+        vec![(item, false)]
+    }
+}
+
+/// A row of items along the width of a container.
+#[derive(Debug)]
+struct Shelf {
+    y: i64,
+    height: u32,
+    used_width: u32,
+}
+
+/// What's left of one container, as each heuristic keeps track of it.
+#[derive(Debug)]
+enum FreeSpace {
+    Shelf {
+        size: Rectangle,
+        shelves: Vec<Shelf>,
+    },
+    Guillotine(Vec<PositionedRectangle>),
+    MaxRects(Vec<PositionedRectangle>),
+}
+
+impl FreeSpace {
+    fn new(size: Rectangle, heuristic: Heuristic) -> Self {
+        let whole = size.at(Point::default());
+        match heuristic {
+            Heuristic::Shelf => FreeSpace::Shelf {
+                size,
+                shelves: Vec::new(),
+            },
+            Heuristic::Guillotine => FreeSpace::Guillotine(vec![whole]),
+            Heuristic::MaxRects => FreeSpace::MaxRects(vec![whole]),
+        }
+    }
+
+    /// Finds room for `item` and takes it, returning where it went and whether it was turned.
+    fn insert(
+        &mut self,
+        item: Rectangle,
+        allow_rotation: bool,
+    ) -> Option<(PositionedRectangle, bool)> {
+        let orientations = orientations(item, allow_rotation);
+        match self {
+            FreeSpace::Shelf { size, shelves } => insert_shelf(*size, shelves, &orientations),
+            FreeSpace::Guillotine(free) => insert_guillotine(free, &orientations),
+            FreeSpace::MaxRects(free) => insert_max_rects(free, &orientations),
+        }
+    }
+}
+
+/// Puts the item on the first shelf with room for it, or on a new shelf above the others.
+fn insert_shelf(
+    size: Rectangle,
+    shelves: &mut Vec<Shelf>,
+    orientations: &[(Rectangle, bool)],
+) -> Option<(PositionedRectangle, bool)> {
+    for shelf in shelves.iter_mut() {
+        let room = Rectangle {
+            width: size.width - shelf.used_width,
+            height: shelf.height,
+        };
+        // Of the orientations that fit, the tallest wastes the least of the shelf's height
+        let fitting = orientations
+            .iter()
+            .filter(|(oriented, _)| room.can_fit(oriented))
+            .max_by_key(|(oriented, _)| oriented.height);
+        if let Some(&(oriented, rotated)) = fitting {
+            let position = oriented.at(Point::new(i64::from(shelf.used_width), shelf.y));
+            shelf.used_width += oriented.width;
+            return Some((position, rotated));
+        }
+    }
+
+    let y = shelves
+        .last()
+        .map_or(0, |shelf| shelf.y + i64::from(shelf.height));
+    let room = Rectangle {
+        width: size.width,
+        height: size.height - u32::try_from(y).ok()?,
+    };
+    // A new shelf is as high as its first item, so keep that low
+    let &(oriented, rotated) = orientations
+        .iter()
+        .filter(|(oriented, _)| room.can_fit(oriented))
+        .min_by_key(|(oriented, _)| oriented.height)?;
+    shelves.push(Shelf {
+        y,
+        height: oriented.height,
+        used_width: oriented.width,
+    });
+    Some((oriented.at(Point::new(0, y)), rotated))
+}
+
+/// Every way to put the item in a free rectangle, as `(free index, oriented item, rotated)`.
+fn candidates<'a>(
+    free: &'a [PositionedRectangle],
+    orientations: &'a [(Rectangle, bool)],
+) -> impl Iterator<Item = (usize, Rectangle, bool)> + 'a {
+    free.iter().enumerate().flat_map(move |(index, space)| {
+        orientations
+            .iter()
+            .filter(|(oriented, _)| space.size.can_fit(oriented))
+            .map(move |&(oriented, rotated)| (index, oriented, rotated))
+    })
+}
+
+/// Puts the item in the top left corner of the free rectangle it fits best, and cuts what's left
+/// of that rectangle in two along the shorter leftover side.
+fn insert_guillotine(
+    free: &mut Vec<PositionedRectangle>,
+    orientations: &[(Rectangle, bool)],
+) -> Option<(PositionedRectangle, bool)> {
+    // The best fit leaves the least area over
+    let (index, oriented, rotated) = candidates(free, orientations)
+        .min_by_key(|&(index, oriented, _)| free[index].size.area() - oriented.area())?;
+    let space = free.swap_remove(index);
+    let placed = oriented.at(space.origin);
+
+    let leftover_width = space.size.width - oriented.width;
+    let leftover_height = space.size.height - oriented.height;
+    // Cut along the shorter leftover, so the larger leftover keeps the full side of the space
+    let (right_height, below_width) = if leftover_width < leftover_height {
+        (oriented.height, space.size.width)
+    } else {
+        (space.size.height, oriented.width)
+    };
+    let right =
+        PositionedRectangle::new(placed.right(), space.top(), leftover_width, right_height);
+    let below =
+        PositionedRectangle::new(space.left(), placed.bottom(), below_width, leftover_height);
+    free.extend([right, below].into_iter().filter(|rest| !rest.is_empty()));
+
+    Some((placed, rotated))
+}
+
+/// Puts the item where its shorter leftover side is smallest, then replaces every free rectangle
+/// it covers part of by the largest free rectangles around it.
+fn insert_max_rects(
+    free: &mut Vec<PositionedRectangle>,
+    orientations: &[(Rectangle, bool)],
+) -> Option<(PositionedRectangle, bool)> {
+    let (index, oriented, rotated) = candidates(free, orientations)
+        .min_by_key(|&(index, oriented, _)| {
+            let space = &free[index].size;
+            let leftover_width = space.width - oriented.width;
+            let leftover_height = space.height - oriented.height;
+            (
+                leftover_width.min(leftover_height),
+                leftover_width.max(leftover_height),
+            )
+        })?;
+    let placed = oriented.at(free[index].origin);
+
+    let mut split = Vec::with_capacity(free.len() + 4);
+    for space in free.drain(..) {
+        if !space.overlaps(&placed) {
+            split.push(space);
+            continue;
+        }
+        let pieces = [
+            // Left of, right of, above and below the item, each as large as the space allows
+            PositionedRectangle::from_corners(
+                space.origin,
+                Point::new(placed.left(), space.bottom()),
+            ),
+            PositionedRectangle::from_corners(
+                Point::new(placed.right(), space.top()),
+                Point::new(space.right(), space.bottom()),
+            ),
+            PositionedRectangle::from_corners(
+                space.origin,
+                Point::new(space.right(), placed.top()),
+            ),
+            PositionedRectangle::from_corners(
+                Point::new(space.left(), placed.bottom()),
+                Point::new(space.right(), space.bottom()),
+            ),
+        ];
+        split.extend(pieces.into_iter().filter(|piece| {
+            !piece.is_empty() && space.contains(piece)
+        }));
+    }
+
+    // Drop the rectangles that lie within others; they add nothing
+    for (index, space) in split.iter().enumerate() {
+        let covered = split.iter().enumerate().any(|(other_index, other)| {
+            other_index != index
+                && other.contains(space)
+                && (other != space || other_index < index)
+        });
+        if !covered {
+            free.push(*space);
+        }
+    }
+
+    Some((placed, rotated))
+}
@@ -0,0 +1,606 @@
+//! Shapes of different kinds behind one trait, so a scene can hold them all.
+//!
+//! Shapes use `f64` coordinates, growing right and down as in `geometry` and in SVG. Rectangles keep
+//! whole-number coordinates, so moving or scaling them rounds.
+//!
+//! A `Rectangle` has a size but no place: as a shape it always sits with its top-left corner at
+//! (0, 0), and `translate` leaves it there. To move one, give it a place first with
+//! `Rectangle::at`, or `PositionedRectangle::from` for the origin.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::geometry::PositionedRectangle;
+use crate::Rectangle;
+
+/// A point on the plane, with fractional coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vertex {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vertex {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn distance(&self, other: &Vertex) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+
+    fn translated(&self, dx: f64, dy: f64) -> Vertex {
+        Vertex::new(self.x + dx, self.y + dy)
+    }
+
+    fn scaled(&self, factor: f64) -> Vertex {
+        Vertex::new(self.x * factor, self.y * factor)
+    }
+}
+
+/// The smallest upright box around a shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min: Vertex,
+    pub max: Vertex,
+}
+
+impl BoundingBox {
+    /// The box around all the vertices, or `None` if there are none.
+    pub fn around(vertices: impl IntoIterator<Item = Vertex>) -> Option<BoundingBox> {
+        vertices
+            .into_iter()
+            .map(|vertex| BoundingBox {
+                min: vertex,
+                max: vertex,
+            })
+            .reduce(|bounds, other| bounds.union(&other))
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width() * self.height()
+    }
+
+    /// The box around both boxes.
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            min: Vertex::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Vertex::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+}
+
+/// Something with an outline on the plane.
+pub trait Shape {
+    /// Returns what kind of shape this is, like "circle".
+    fn kind(&self) -> &str;
+
+    fn area(&self) -> f64;
+
+    fn perimeter(&self) -> f64;
+
+    fn bounding_box(&self) -> BoundingBox;
+
+    /// Moves the shape by `dx` and `dy`.
+    fn translate(&mut self, dx: f64, dy: f64);
+
+    /// Scales the shape by a positive `factor`, away from or toward (0, 0), so a scene scales as
+    /// a whole.
+    ///
+    /// # Panics
+    ///
+    /// If `factor` is zero, negative, infinite or NaN: such a shape would collapse, turn inside
+    /// out or lose its coordinates.
+    fn scale(&mut self, factor: f64);
+
+    /// Returns the shape as an SVG element, without styling.
+    fn to_svg(&self) -> String;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    /// A polygon needs at least three vertices; it got this many.
+    TooFewVertices(usize),
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShapeError::TooFewVertices(count) => {
+                write!(f, "a polygon needs at least 3 vertices, not {count}")
+            }
+        }
+    }
+}
+
+impl Error for ShapeError {}
+
+impl Shape for PositionedRectangle {
+    fn kind(&self) -> &str {
+        "rectangle"
+    }
+
+    fn area(&self) -> f64 {
+        f64::from(self.size.width) * f64::from(self.size.height)
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * (f64::from(self.size.width) + f64::from(self.size.height))
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox {
+            min: Vertex::new(self.left() as f64, self.top() as f64),
+            max: Vertex::new(self.right() as f64, self.bottom() as f64),
+        }
+    }
+
+    fn translate(&mut self, dx: f64, dy: f64) {
+        *self = self.translated(dx.round() as i64, dy.round() as i64);
+    }
+
+    fn scale(&mut self, factor: f64) {
+        check_factor(factor);
+        self.origin.x = (self.origin.x as f64 * factor).round() as i64;
+        self.origin.y = (self.origin.y as f64 * factor).round() as i64;
+        self.size.width = (f64::from(self.size.width) * factor).round() as u32;
+        self.size.height = (f64::from(self.size.height) * factor).round() as u32;
+    }
+
+    fn to_svg(&self) -> String {
+        format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+            self.left(),
+            self.top(),
+            self.size.width,
+            self.size.height
+        )
+    }
+}
+
+/// A rectangle at the origin, the way `PositionedRectangle::from` would place it.
+impl Shape for Rectangle {
+    fn kind(&self) -> &str {
+        "rectangle"
+    }
+
+    fn area(&self) -> f64 {
+        Shape::area(&PositionedRectangle::from(*self))
+    }
+
+    fn perimeter(&self) -> f64 {
+        Shape::perimeter(&PositionedRectangle::from(*self))
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        PositionedRectangle::from(*self).bounding_box()
+    }
+
+    /// Does nothing: a `Rectangle` has no position to change, so it stays at the origin. Place it
+    /// with `Rectangle::at` to be able to move it.
+    fn translate(&mut self, _dx: f64, _dy: f64) {}
+
+    /// Scales the size; the top-left corner stays at the origin.
+    fn scale(&mut self, factor: f64) {
+        let mut placed = PositionedRectangle::from(*self);
+        placed.scale(factor);
+        *self = placed.size;
+    }
+
+    fn to_svg(&self) -> String {
+        PositionedRectangle::from(*self).to_svg()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Vertex,
+    pub radius: f64,
+}
+
+impl Circle {
+    pub fn new(center: Vertex, radius: f64) -> Self {
+        Self { center, radius }
+    }
+}
+
+impl Shape for Circle {
+    fn kind(&self) -> &str {
+        "circle"
+    }
+
+    fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * PI * self.radius
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox {
+            min: self.center.translated(-self.radius, -self.radius),
+            max: self.center.translated(self.radius, self.radius),
+        }
+    }
+
+    fn translate(&mut self, dx: f64, dy: f64) {
+        self.center = self.center.translated(dx, dy);
+    }
+
+    fn scale(&mut self, factor: f64) {
+        check_factor(factor);
+        self.center = self.center.scaled(factor);
+        self.radius *= factor;
+    }
+
+    fn to_svg(&self) -> String {
+        format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"/>",
+            number(self.center.x),
+            number(self.center.y),
+            number(self.radius)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub vertices: [Vertex; 3],
+}
+
+impl Triangle {
+    pub fn new(a: Vertex, b: Vertex, c: Vertex) -> Self {
+        Self {
+            vertices: [a, b, c],
+        }
+    }
+}
+
+impl Shape for Triangle {
+    fn kind(&self) -> &str {
+        "triangle"
+    }
+
+    fn area(&self) -> f64 {
+        shoelace(&self.vertices)
+    }
+
+    fn perimeter(&self) -> f64 {
+        outline_length(&self.vertices)
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::around(self.vertices).expect("a triangle has vertices")
+    }
+
+    fn translate(&mut self, dx: f64, dy: f64) {
+        for vertex in &mut self.vertices {
+            *vertex = vertex.translated(dx, dy);
+        }
+    }
+
+    fn scale(&mut self, factor: f64) {
+        check_factor(factor);
+        for vertex in &mut self.vertices {
+            *vertex = vertex.scaled(factor);
+        }
+    }
+
+    fn to_svg(&self) -> String {
+        polygon_svg(&self.vertices)
+    }
+}
+
+/// A closed outline through its vertices, in order. It shouldn't cross itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    vertices: Vec<Vertex>,
+}
+
+impl Polygon {
+    /// Creates a polygon through `vertices`, in order.
+    ///
+    /// # Errors
+    ///
+    /// * `ShapeError::TooFewVertices` - If there are fewer than three vertices.
+    pub fn new(vertices: Vec<Vertex>) -> Result<Self, ShapeError> {
+        if vertices.len() < 3 {
+            return Err(ShapeError::TooFewVertices(vertices.len()));
+        }
+        Ok(Self { vertices })
+    }
+
+    /// Creates a regular polygon, like a hexagon, with its first vertex straight up.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - The center of the polygon.
+    /// * `radius` - The distance from the center to each vertex.
+    /// * `sides` - The number of sides.
+    ///
+    /// # Errors
+    ///
+    /// * `ShapeError::TooFewVertices` - If `sides` is under three.
+    pub fn regular(center: Vertex, radius: f64, sides: usize) -> Result<Self, ShapeError> {
+        let vertices = (0..sides)
+            .map(|index| {
+                let angle = 2.0 * PI * index as f64 / sides as f64 - PI / 2.0;
+                center.translated(radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+        Self::new(vertices)
+    }
+
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+}
+
+impl Shape for Polygon {
+    fn kind(&self) -> &str {
+        "polygon"
+    }
+
+    fn area(&self) -> f64 {
+        shoelace(&self.vertices)
+    }
+
+    fn perimeter(&self) -> f64 {
+        outline_length(&self.vertices)
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::around(self.vertices.iter().copied()).expect("a polygon has vertices")
+    }
+
+    fn translate(&mut self, dx: f64, dy: f64) {
+        for vertex in &mut self.vertices {
+            *vertex = vertex.translated(dx, dy);
+        }
+    }
+
+    fn scale(&mut self, factor: f64) {
+        check_factor(factor);
+        for vertex in &mut self.vertices {
+            *vertex = vertex.scaled(factor);
+        }
+    }
+
+    fn to_svg(&self) -> String {
+        polygon_svg(&self.vertices)
+    }
+}
+
+/// Panics unless `factor` is one a shape can be scaled by.
+fn check_factor(factor: f64) {
+    assert!(
+        factor > 0.0 && factor.is_finite(),
+        "shapes can only be scaled by a positive, finite factor, not {factor}"
+    );
+}
+
+/// The area inside a closed outline, by the shoelace formula.
+fn shoelace(vertices: &[Vertex]) -> f64 {
+    let twice_area: f64 = edges(vertices)
+        .map(|(from, to)| from.x * to.y - to.x * from.y)
+        .sum();
+    twice_area.abs() / 2.0
+}
+
+fn outline_length(vertices: &[Vertex]) -> f64 {
+    edges(vertices).map(|(from, to)| from.distance(to)).sum()
+}
+
+/// Each edge of a closed outline, including the one back to the first vertex.
+fn edges(vertices: &[Vertex]) -> impl Iterator<Item = (&Vertex, &Vertex)> {
+    vertices.iter().zip(vertices.iter().cycle().skip(1))
+}
+
+fn polygon_svg(vertices: &[Vertex]) -> String {
+    let points: Vec<String> = vertices
+        .iter()
+        .map(|vertex| format!("{},{}", number(vertex.x), number(vertex.y)))
+        .collect();
+    format!("<polygon points=\"{}\"/>", points.join(" "))
+}
+
+/// Writes a coordinate for SVG, with at most three decimals and no trailing zeros.
+fn number(value: f64) -> String {
+    let rounded = format!("{value:.3}");
+    let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" => String::from("0"),
+        trimmed => String::from(trimmed),
+    }
+}
+
+/// Totals over the shapes of a scene.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Statistics {
+    pub count: usize,
+    pub total_area: f64,
+    pub total_perimeter: f64,
+    /// The smallest and largest area of a shape, if there are any shapes.
+    pub area_range: Option<(f64, f64)>,
+    /// The number of shapes and their total area, by kind.
+    pub by_kind: BTreeMap<String, (usize, f64)>,
+    /// The box around every shape, if there are any.
+    pub bounding_box: Option<BoundingBox>,
+}
+
+impl Statistics {
+    pub fn mean_area(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.total_area / self.count as f64
+        }
+    }
+
+    /// The total area over the area of the bounding box. Overlapping shapes can push it past 1.
+    pub fn coverage(&self) -> f64 {
+        match self.bounding_box {
+            Some(bounds) if bounds.area() > 0.0 => self.total_area / bounds.area(),
+            _ => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} shape(s), total area {:.2}, mean area {:.2}, total perimeter {:.2}",
+            self.count,
+            self.total_area,
+            self.mean_area(),
+            self.total_perimeter
+        )?;
+        for (kind, (count, area)) in &self.by_kind {
+            writeln!(f, "- {kind}: {count}, area {area:.2}")?;
+        }
+        write!(f, "Coverage of the bounding box: {:.1}%", self.coverage() * 100.0)
+    }
+}
+
+/// Shapes of any kind, drawn in order.
+#[derive(Default)]
+pub struct Scene {
+    shapes: Vec<Box<dyn Shape>>,
+}
+
+impl Scene {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, shape: impl Shape + 'static) {
+        self.shapes.push(Box::new(shape));
+    }
+
+    pub fn push_boxed(&mut self, shape: Box<dyn Shape>) {
+        self.shapes.push(shape);
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Shape> {
+        self.shapes.iter().map(|shape| shape.as_ref())
+    }
+
+    /// The box around every shape, or `None` for an empty scene.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        self.iter()
+            .map(|shape| shape.bounding_box())
+            .reduce(|bounds, other| bounds.union(&other))
+    }
+
+    pub fn translate(&mut self, dx: f64, dy: f64) {
+        for shape in &mut self.shapes {
+            shape.translate(dx, dy);
+        }
+    }
+
+    /// Scales every shape by `factor` from (0, 0), so they keep their places relative to each
+    /// other.
+    ///
+    /// # Panics
+    ///
+    /// If `factor` isn't a positive, finite number, as for `Shape::scale`.
+    pub fn scale(&mut self, factor: f64) {
+        check_factor(factor);
+        for shape in &mut self.shapes {
+            shape.scale(factor);
+        }
+    }
+
+    pub fn statistics(&self) -> Statistics {
+        let mut statistics = Statistics {
+            bounding_box: self.bounding_box(),
+            ..Statistics::default()
+        };
+
+        for shape in self.iter() {
+            let area = shape.area();
+            statistics.count += 1;
+            statistics.total_area += area;
+            statistics.total_perimeter += shape.perimeter();
+            statistics.area_range = Some(match statistics.area_range {
+                Some((smallest, largest)) => (smallest.min(area), largest.max(area)),
+                None => (area, area),
+            });
+
+            let kind = statistics
+                .by_kind
+                .entry(String::from(shape.kind()))
+                .or_default();
+            kind.0 += 1;
+            kind.1 += area;
+        }
+
+        statistics
+    }
+
+    /// Draws the scene as an SVG document, outlined in black and framed by `margin` on each side.
+    pub fn to_svg(&self, margin: f64) -> String {
+        let bounds = self.bounding_box().unwrap_or(BoundingBox {
+            min: Vertex::default(),
+            max: Vertex::default(),
+        });
+        let width = number(bounds.width() + 2.0 * margin);
+        let height = number(bounds.height() + 2.0 * margin);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {width} {height}\" \
+             width=\"{width}\" height=\"{height}\">\n",
+            number(bounds.min.x - margin),
+            number(bounds.min.y - margin)
+        );
+        svg.push_str("  <g fill=\"none\" stroke=\"black\">\n");
+        for shape in self.iter() {
+            svg.push_str(&format!("    {}\n", shape.to_svg()));
+        }
+        svg.push_str("  </g>\n</svg>\n");
+        svg
+    }
+}
+
+impl From<Vec<Box<dyn Shape>>> for Scene {
+    fn from(shapes: Vec<Box<dyn Shape>>) -> Self {
+        Self { shapes }
+    }
+}
+
+impl FromIterator<Box<dyn Shape>> for Scene {
+    fn from_iter<I: IntoIterator<Item = Box<dyn Shape>>>(iter: I) -> Self {
+        Self {
+            shapes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for Scene {
+    type Item = Box<dyn Shape>;
+    type IntoIter = std::vec::IntoIter<Box<dyn Shape>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.shapes.into_iter()
+    }
+}
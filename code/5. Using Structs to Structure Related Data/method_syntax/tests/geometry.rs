@@ -0,0 +1,115 @@
+use method_syntax::geometry::{Point, PositionedRectangle};
+use method_syntax::Rectangle;
+use method_syntax::shapes::Shape;
+
+fn rectangle(width: u32, height: u32) -> Rectangle {
+    Rectangle { width, height }
+}
+
+#[test]
+fn perimeter_and_rotation() {
+    assert_eq!(rectangle(30, 50).perimeter(), 160);
+    assert_eq!(rectangle(30, 50).rotated(), rectangle(50, 30));
+    assert_eq!(Rectangle::square(3).rotated(), Rectangle::square(3));
+}
+
+#[test]
+fn can_hold_variants() {
+    let rect1 = rectangle(30, 50);
+    let tall = rectangle(20, 40);
+    let wide = rectangle(40, 20);
+
+    assert!(rect1.can_hold(&tall));
+    assert!(!rect1.can_hold(&wide));
+    assert!(rect1.can_hold_rotated(&wide));
+
+    // `can_hold` is strict; `can_fit` lets a rectangle hold one of the same size
+    assert!(!rect1.can_hold(&rect1));
+    assert!(rect1.can_fit(&rect1));
+    assert!(!rect1.can_fit(&rectangle(50, 30)));
+    assert!(rect1.can_fit_rotated(&rectangle(50, 30)));
+    assert!(!rect1.can_fit_rotated(&rectangle(31, 31)));
+}
+
+#[test]
+fn edges_and_points() {
+    let placed = PositionedRectangle::new(-5, 10, 10, 20);
+
+    assert_eq!((placed.left(), placed.top()), (-5, 10));
+    assert_eq!((placed.right(), placed.bottom()), (5, 30));
+    assert_eq!(placed.area(), 200);
+    assert_eq!(placed.perimeter(), 60);
+    assert_eq!(rectangle(10, 20).at(Point::new(-5, 10)), placed);
+
+    assert!(placed.contains_point(Point::new(-5, 10)));
+    assert!(placed.contains_point(Point::new(4, 29)));
+    assert!(!placed.contains_point(Point::new(5, 29)));
+    assert!(!placed.contains_point(Point::new(0, 30)));
+}
+
+#[test]
+fn containment_overlap_and_intersection() {
+    let a = PositionedRectangle::new(0, 0, 10, 10);
+    let b = PositionedRectangle::new(5, 5, 10, 10);
+    let inside = PositionedRectangle::new(2, 2, 8, 8);
+    let touching = PositionedRectangle::new(10, 0, 5, 5);
+
+    assert!(a.contains(&inside));
+    assert!(a.contains(&a));
+    assert!(!a.contains(&b));
+
+    assert!(a.overlaps(&b));
+    assert!(!a.overlaps(&touching));
+    assert_eq!(a.intersection(&b), Some(PositionedRectangle::new(5, 5, 5, 5)));
+    assert_eq!(a.intersection(&touching), None);
+    assert_eq!(a.intersection(&inside), Some(inside));
+}
+
+#[test]
+fn unions_and_bounding_boxes() {
+    let a = PositionedRectangle::new(0, 0, 10, 10);
+    let b = PositionedRectangle::new(20, -5, 5, 5);
+
+    assert_eq!(a.union(&b), PositionedRectangle::new(0, -5, 25, 15));
+    assert_eq!(a.union(&a), a);
+    assert_eq!(
+        PositionedRectangle::bounding_box(&[a, b, a.translated(-3, 40)]),
+        Some(PositionedRectangle::new(-3, -5, 28, 55))
+    );
+    assert_eq!(PositionedRectangle::bounding_box(&[]), None);
+}
+
+#[test]
+fn moving_past_the_edge_of_the_plane_stops_there() {
+    let a = PositionedRectangle::new(10, -10, 5, 5);
+    let at_the_edge = PositionedRectangle::new(i64::MAX, i64::MIN, 5, 5);
+
+    assert_eq!(a.translated(i64::MAX, i64::MIN), at_the_edge);
+    let mut shape = a;
+    shape.translate(1e300, -1e300);
+    assert_eq!(shape, at_the_edge);
+}
+
+#[test]
+fn corners_can_come_in_any_order() {
+    let rect = PositionedRectangle::from_corners(Point::new(10, 0), Point::new(0, 5));
+
+    assert_eq!(rect, PositionedRectangle::new(0, 0, 10, 5));
+    assert!(PositionedRectangle::from_corners(Point::new(1, 1), Point::new(1, 9)).is_empty());
+}
+
+#[test]
+fn huge_rectangles_do_not_overflow() {
+    let huge = PositionedRectangle::new(i64::MAX - 10, 0, u32::MAX, u32::MAX);
+
+    assert_eq!(huge.area(), u64::from(u32::MAX) * u64::from(u32::MAX));
+    assert_eq!(huge.perimeter(), 4 * u64::from(u32::MAX));
+    assert_eq!(rectangle(u32::MAX, 1).perimeter(), 2 * u64::from(u32::MAX) + 2);
+    assert_eq!(huge.right(), i64::MAX);
+    assert!(huge.contains_point(Point::new(i64::MAX - 1, 0)));
+
+    // Corners too far apart are cut off at the largest size a rectangle can have
+    let far = Point::new(i64::from(u32::MAX), 1);
+    let wide = PositionedRectangle::from_corners(Point::new(-1, 0), far);
+    assert_eq!(wide, PositionedRectangle::new(-1, 0, u32::MAX, 1));
+}
@@ -0,0 +1,161 @@
+use method_syntax::geometry::PositionedRectangle;
+use method_syntax::packing::{pack, Heuristic, Options, Packing};
+use method_syntax::Rectangle;
+
+const HEURISTICS: [Heuristic; 3] = [Heuristic::Shelf, Heuristic::Guillotine, Heuristic::MaxRects];
+
+fn rectangle(width: u32, height: u32) -> Rectangle {
+    Rectangle { width, height }
+}
+
+fn options(heuristic: Heuristic) -> Options {
+    Options {
+        heuristic,
+        ..Options::default()
+    }
+}
+
+/// Rectangles of assorted sizes, the same every run.
+fn assorted(count: usize) -> Vec<Rectangle> {
+    let mut seed: u64 = 42;
+    let mut next = move |limit: u32| {
+        seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+        (seed >> 33) as u32 % limit + 1
+    };
+    (0..count).map(|_| rectangle(next(40), next(40))).collect()
+}
+
+/// Checks that every item was placed once, at its size, inside its container, without overlaps.
+fn assert_valid(packing: &Packing, items: &[Rectangle]) {
+    let mut seen = vec![0; items.len()];
+    for &item in &packing.unplaced {
+        seen[item] += 1;
+    }
+
+    for container in &packing.containers {
+        let bounds = container.size.at(Default::default());
+        for (index, placement) in container.placements.iter().enumerate() {
+            seen[placement.item] += 1;
+            let item = items[placement.item];
+            let expected = if placement.rotated { item.rotated() } else { item };
+            assert_eq!(placement.position.size, expected);
+            assert!(bounds.contains(&placement.position), "{placement:?} is outside");
+
+            for other in &container.placements[index + 1..] {
+                assert!(
+                    !placement.position.overlaps(&other.position),
+                    "{placement:?} overlaps {other:?}"
+                );
+            }
+        }
+    }
+
+    assert!(seen.iter().all(|&count| count == 1), "items placed more or less than once");
+}
+
+#[test]
+fn exact_fits_fill_the_container() {
+    let items = [Rectangle::square(5); 4];
+
+    for heuristic in HEURISTICS {
+        let packing = pack(Rectangle::square(10), &items, options(heuristic));
+        assert_valid(&packing, &items);
+        assert_eq!(packing.containers.len(), 1, "{heuristic:?}");
+        assert_eq!(packing.utilization(), 1.0);
+    }
+}
+
+#[test]
+fn every_heuristic_packs_validly() {
+    let items = assorted(60);
+    let container = rectangle(100, 80);
+
+    for heuristic in HEURISTICS {
+        for allow_rotation in [false, true] {
+            let options = Options {
+                heuristic,
+                allow_rotation,
+                max_containers: None,
+            };
+            let packing = pack(container, &items, options);
+            assert_valid(&packing, &items);
+            assert!(packing.unplaced.is_empty());
+            assert!(packing.utilization() > 0.5, "{heuristic:?}: {packing}");
+        }
+    }
+}
+
+#[test]
+fn max_rects_packs_at_least_as_tightly_as_shelves() {
+    let items = assorted(60);
+    let container = rectangle(100, 80);
+
+    let shelf = pack(container, &items, options(Heuristic::Shelf));
+    let max_rects = pack(container, &items, options(Heuristic::MaxRects));
+    assert!(max_rects.containers.len() <= shelf.containers.len());
+}
+
+#[test]
+fn rotation_lets_items_fit() {
+    let items = [rectangle(30, 10)];
+    let container = rectangle(10, 30);
+
+    for heuristic in HEURISTICS {
+        let upright = Options {
+            allow_rotation: false,
+            ..options(heuristic)
+        };
+        assert_eq!(pack(container, &items, upright).unplaced, [0]);
+
+        let packing = pack(container, &items, options(heuristic));
+        assert!(packing.containers[0].placements[0].rotated);
+        assert_eq!(
+            packing.containers[0].placements[0].position,
+            PositionedRectangle::new(0, 0, 10, 30)
+        );
+    }
+}
+
+#[test]
+fn items_spill_into_more_containers_up_to_the_limit() {
+    let items = [Rectangle::square(6); 5];
+    let container = Rectangle::square(10);
+
+    for heuristic in HEURISTICS {
+        let packing = pack(container, &items, options(heuristic));
+        assert_valid(&packing, &items);
+        assert_eq!(packing.containers.len(), 5);
+
+        let limited = Options {
+            max_containers: Some(2),
+            ..options(heuristic)
+        };
+        let packing = pack(container, &items, limited);
+        assert_valid(&packing, &items);
+        assert_eq!(packing.placed(), 2);
+        assert_eq!(packing.unplaced.len(), 3);
+    }
+}
+
+#[test]
+fn items_larger_than_a_container_are_left_out() {
+    let items = [rectangle(5, 5), rectangle(11, 2), rectangle(2, 3)];
+    let packing = pack(Rectangle::square(10), &items, Options::default());
+
+    assert_valid(&packing, &items);
+    assert_eq!(packing.unplaced, [1]);
+    assert_eq!(packing.containers[0].used_area(), 31);
+    assert_eq!(
+        packing.to_string(),
+        "Container 1: 2 item(s), 31.0% used\n\
+         Overall: 2 item(s) in 1 container(s), 31.0% used; 1 item(s) didn't fit"
+    );
+}
+
+#[test]
+fn nothing_to_pack_uses_no_containers() {
+    let packing = pack(Rectangle::square(10), &[], Options::default());
+
+    assert!(packing.containers.is_empty());
+    assert_eq!(packing.utilization(), 0.0);
+}
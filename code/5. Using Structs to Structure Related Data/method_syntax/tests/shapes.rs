@@ -0,0 +1,193 @@
+use method_syntax::geometry::PositionedRectangle;
+use method_syntax::Rectangle;
+use method_syntax::shapes::{
+    BoundingBox, Circle, Polygon, Scene, Shape, ShapeError, Triangle, Vertex,
+};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{actual} isn't {expected}");
+}
+
+fn right_triangle() -> Triangle {
+    Triangle::new(Vertex::new(0.0, 0.0), Vertex::new(3.0, 0.0), Vertex::new(0.0, 4.0))
+}
+
+#[test]
+fn rectangles_are_shapes_once_placed() {
+    let mut rectangle = PositionedRectangle::new(1, 2, 30, 50);
+    let shape: &dyn Shape = &rectangle;
+
+    assert_eq!(shape.kind(), "rectangle");
+    assert_close(shape.area(), 1500.0);
+    assert_close(shape.perimeter(), 160.0);
+    assert_eq!(
+        shape.bounding_box(),
+        BoundingBox {
+            min: Vertex::new(1.0, 2.0),
+            max: Vertex::new(31.0, 52.0)
+        }
+    );
+
+    Shape::translate(&mut rectangle, 1.6, -2.0);
+    Shape::scale(&mut rectangle, 0.5);
+    assert_eq!(rectangle, PositionedRectangle::new(2, 0, 15, 25));
+    assert_eq!(rectangle.to_svg(), "<rect x=\"2\" y=\"0\" width=\"15\" height=\"25\"/>");
+
+    let at_origin = PositionedRectangle::from(Rectangle { width: 3, height: 4 });
+    assert_eq!(at_origin, PositionedRectangle::new(0, 0, 3, 4));
+}
+
+#[test]
+fn bare_rectangles_are_shapes_at_the_origin() {
+    let mut rectangle = Rectangle { width: 30, height: 50 };
+    let shape: &dyn Shape = &rectangle;
+
+    assert_eq!(shape.kind(), "rectangle");
+    assert_close(shape.area(), 1500.0);
+    assert_close(shape.perimeter(), 160.0);
+    assert_eq!(
+        shape.bounding_box(),
+        BoundingBox {
+            min: Vertex::new(0.0, 0.0),
+            max: Vertex::new(30.0, 50.0)
+        }
+    );
+
+    // Without a place of its own it stays at the origin, and scales from there
+    Shape::translate(&mut rectangle, 10.0, 10.0);
+    Shape::scale(&mut rectangle, 0.5);
+    assert_eq!(rectangle, Rectangle { width: 15, height: 25 });
+    assert_eq!(rectangle.to_svg(), "<rect x=\"0\" y=\"0\" width=\"15\" height=\"25\"/>");
+
+    let mut scene = Scene::new();
+    scene.add(rectangle);
+    assert_close(scene.statistics().total_area, 375.0);
+}
+
+#[test]
+#[should_panic(expected = "positive, finite factor")]
+fn rectangles_cannot_be_scaled_by_a_negative_factor() {
+    Shape::scale(&mut PositionedRectangle::new(0, 0, 3, 4), -1.0);
+}
+
+#[test]
+#[should_panic(expected = "positive, finite factor")]
+fn circles_cannot_be_scaled_by_nan() {
+    Circle::new(Vertex::new(0.0, 0.0), 1.0).scale(f64::NAN);
+}
+
+#[test]
+#[should_panic(expected = "positive, finite factor")]
+fn scenes_cannot_be_scaled_to_nothing() {
+    Scene::new().scale(0.0);
+}
+
+#[test]
+fn circles() {
+    let mut circle = Circle::new(Vertex::new(5.0, 5.0), 2.0);
+
+    assert_close(circle.area(), std::f64::consts::PI * 4.0);
+    assert_close(circle.perimeter(), std::f64::consts::PI * 4.0);
+    assert_eq!(circle.bounding_box().min, Vertex::new(3.0, 3.0));
+
+    circle.scale(2.0);
+    circle.translate(-10.0, 0.5);
+    assert_eq!(circle, Circle::new(Vertex::new(0.0, 10.5), 4.0));
+    assert_eq!(circle.to_svg(), "<circle cx=\"0\" cy=\"10.5\" r=\"4\"/>");
+}
+
+#[test]
+fn triangles() {
+    let mut triangle = right_triangle();
+
+    assert_close(triangle.area(), 6.0);
+    assert_close(triangle.perimeter(), 12.0);
+    assert_eq!(triangle.bounding_box().max, Vertex::new(3.0, 4.0));
+
+    triangle.translate(1.0, 1.0);
+    assert_eq!(triangle.to_svg(), "<polygon points=\"1,1 4,1 1,5\"/>");
+}
+
+#[test]
+fn polygons() {
+    let square = Polygon::new(vec![
+        Vertex::new(0.0, 0.0),
+        Vertex::new(2.0, 0.0),
+        Vertex::new(2.0, 2.0),
+        Vertex::new(0.0, 2.0),
+    ])
+    .unwrap();
+    assert_close(square.area(), 4.0);
+    assert_close(square.perimeter(), 8.0);
+
+    // A regular hexagon is six equilateral triangles
+    let hexagon = Polygon::regular(Vertex::new(0.0, 0.0), 1.0, 6).unwrap();
+    assert_eq!(hexagon.vertices().len(), 6);
+    assert_close(hexagon.area(), 6.0 * 3f64.sqrt() / 4.0);
+    assert_close(hexagon.perimeter(), 6.0);
+
+    assert_eq!(
+        Polygon::new(vec![Vertex::new(0.0, 0.0), Vertex::new(1.0, 1.0)]),
+        Err(ShapeError::TooFewVertices(2))
+    );
+    assert!(Polygon::regular(Vertex::default(), 1.0, 2).is_err());
+}
+
+#[test]
+fn scenes_hold_any_shape_and_sum_them_up() {
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(PositionedRectangle::new(0, 0, 10, 10)),
+        Box::new(right_triangle()),
+        Box::new(PositionedRectangle::new(10, 0, 10, 10)),
+    ];
+    let mut scene = Scene::from(shapes);
+    scene.add(Circle::new(Vertex::new(10.0, 20.0), 1.0));
+
+    let statistics = scene.statistics();
+    assert_eq!(statistics.count, 4);
+    assert_close(statistics.total_area, 206.0 + std::f64::consts::PI);
+    assert_eq!(statistics.area_range, Some((std::f64::consts::PI, 100.0)));
+    assert_eq!(statistics.by_kind["rectangle"], (2, 200.0));
+    assert_eq!(statistics.by_kind["triangle"], (1, 6.0));
+    assert_eq!(
+        statistics.bounding_box,
+        Some(BoundingBox {
+            min: Vertex::new(0.0, 0.0),
+            max: Vertex::new(20.0, 21.0)
+        })
+    );
+    assert_close(statistics.coverage(), statistics.total_area / 420.0);
+
+    scene.scale(2.0);
+    scene.translate(-1.0, 0.0);
+    let bounds = scene.bounding_box().unwrap();
+    assert_eq!((bounds.min.x, bounds.max.x, bounds.max.y), (-1.0, 39.0, 42.0));
+}
+
+#[test]
+fn empty_scenes() {
+    let scene = Scene::new();
+
+    assert!(scene.is_empty());
+    assert_eq!(scene.bounding_box(), None);
+    assert_eq!(scene.statistics().mean_area(), 0.0);
+    assert_eq!(scene.statistics().coverage(), 0.0);
+}
+
+#[test]
+fn scenes_export_to_svg() {
+    let mut scene = Scene::new();
+    scene.add(PositionedRectangle::new(0, 0, 10, 5));
+    scene.add(Circle::new(Vertex::new(10.0, 5.0), 2.5));
+
+    assert_eq!(
+        scene.to_svg(1.0),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -1 14.5 9.5\" \
+         width=\"14.5\" height=\"9.5\">\n\
+         \x20 <g fill=\"none\" stroke=\"black\">\n\
+         \x20   <rect x=\"0\" y=\"0\" width=\"10\" height=\"5\"/>\n\
+         \x20   <circle cx=\"10\" cy=\"5\" r=\"2.5\"/>\n\
+         \x20 </g>\n\
+         </svg>\n"
+    );
+}
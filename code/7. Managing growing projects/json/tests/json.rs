@@ -0,0 +1,31 @@
+use json::Json;
+
+#[test]
+fn round_trips() {
+    let text = r#"{"name":"Café \"Corro\"","items":[1,2.5,-3],"open":true,"note":null}"#;
+    let value = Json::parse(text).unwrap();
+
+    assert_eq!(value.get("name").and_then(Json::as_str), Some("Café \"Corro\""));
+    assert_eq!(value.get("items").and_then(Json::as_array).map(<[Json]>::len), Some(3));
+    assert_eq!(Json::parse(&value.to_string()).unwrap(), value);
+    assert!(Json::parse(r#"{"unterminated": "#).is_err());
+    assert!(Json::parse("[1, 2] 3").is_err());
+}
+
+#[test]
+fn combines_surrogate_pairs() {
+    let value = Json::parse(r#""smile \ud83d\ude00 \u00e9""#).unwrap();
+    assert_eq!(value.as_str(), Some("smile \u{1F600} é"));
+    assert!(Json::parse(r#""\ud83d""#).is_err());
+    assert!(Json::parse(r#""\ude00""#).is_err());
+    assert!(Json::parse(r#""\ud83d\u0041""#).is_err());
+}
+
+#[test]
+fn rejects_deep_nesting() {
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(Json::parse(&nested(Json::MAX_DEPTH)).is_ok());
+    assert!(Json::parse(&nested(Json::MAX_DEPTH + 1)).is_err());
+    assert!(Json::parse(&"[".repeat(200_000)).is_err());
+    assert!(Json::parse(&r#"{"a":"#.repeat(200_000)).is_err());
+}
@@ -0,0 +1,467 @@
+//! The order pipeline over HTTP, for the tablets on the tables.
+//!
+//! A small HTTP/1.1 server on top of `std::net`, in the spirit of the web server from chapter
+//! 20: each connection carries one request and gets its own thread. Bodies are JSON.
+//!
+//! | Method | Path                   | Body                                                  |
+//! |--------|------------------------|-------------------------------------------------------|
+//! | GET    | `/menu`                |                                                       |
+//! | POST   | `/orders`              | `{"table": 4, "items": ["Soup"]}`                     |
+//! | GET    | `/orders/{id}`         |                                                       |
+//! | POST   | `/orders/{id}/payment` | `{"method": "cash", "tendered_cents": 5000}`          |
+//!
+//! A payment may add `"tip_cents"`; cards may give `"last_four"` and gift cards their `"code"`.
+//! `tendered_cents` is the cash handed over, or what is left on the card.
+//!
+//! A placed order starts cooking straight away, taking its ingredients out of the pantry;
+//! whatever runs out is sold out on the menu, and an order the pantry can't cover is turned
+//! away with a 409. A kitchen thread then delivers and serves it; poll the order until its
+//! status is `served`, then pay. Errors come back as `{"error": "..."}`.
+
+/// The JSON the API speaks, for callers building requests and reading responses.
+pub use ::json;
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::back_of_house;
+use crate::back_of_house::inventory::Inventory;
+use crate::billing::{Bill, BillingError, Card, Cash, GiftCard, Money, PaymentMethod, Tip};
+use crate::menu::{Menu, MenuItem, Season};
+use crate::order::Order;
+use crate::serving;
+
+use self::json::Json;
+
+/// Requests with a larger body are turned away.
+const MAX_BODY: usize = 64 * 1024;
+
+/// How the restaurant behind the server is set up.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub menu: Menu,
+    pub season: Season,
+    /// Sales tax in basis points.
+    pub tax_rate: u32,
+    /// What the kitchen has to cook with.
+    pub inventory: Inventory,
+}
+
+impl Default for Config {
+    /// The default menu in summer, with 8.25% sales tax and a pantry stocked for 50 of every
+    /// dish.
+    fn default() -> Self {
+        Config {
+            menu: Menu::default(),
+            season: Season::Summer,
+            tax_rate: 825,
+            inventory: Inventory::stocked(Season::Summer, 50),
+        }
+    }
+}
+
+/// Everything the request handlers share.
+struct State {
+    config: Config,
+    orders: HashMap<u32, Order>,
+    next_id: u32,
+}
+
+/// A running server. Call `shutdown` to stop it.
+pub struct Server {
+    address: SocketAddr,
+    stopping: Arc<AtomicBool>,
+    listener: JoinHandle<()>,
+    kitchen: JoinHandle<()>,
+}
+
+impl Server {
+    /// Binds to `address` and starts serving in the background.
+    ///
+    /// Bind to port 0 to let the operating system pick a free port; `address` tells which.
+    pub fn start(address: impl ToSocketAddrs, config: Config) -> io::Result<Server> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+
+        let state = Arc::new(Mutex::new(State {
+            config,
+            orders: HashMap::new(),
+            next_id: 1,
+        }));
+        let stopping = Arc::new(AtomicBool::new(false));
+        let (tickets, kitchen_tickets) = mpsc::channel();
+
+        let kitchen = {
+            let state = Arc::clone(&state);
+            thread::spawn(move || cook(&state, kitchen_tickets))
+        };
+        let listener = {
+            let stopping = Arc::clone(&stopping);
+            thread::spawn(move || listen(&listener, &stopping, &state, tickets))
+        };
+
+        Ok(Server {
+            address,
+            stopping,
+            listener,
+            kitchen,
+        })
+    }
+
+    /// The address the server is listening on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Blocks until the server stops.
+    pub fn wait(self) {
+        let _ = self.listener.join();
+        let _ = self.kitchen.join();
+    }
+
+    /// Stops accepting connections and waits for the kitchen to finish its tickets.
+    pub fn shutdown(self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        // `accept` blocks, so knock on the door once to let the listener notice
+        let _ = TcpStream::connect(self.address);
+        self.wait();
+    }
+}
+
+/// The listener's loop: hand every connection to its own thread until the server stops.
+///
+/// The ticket sender is dropped when the loop ends, which lets the kitchen go home too.
+fn listen(
+    listener: &TcpListener,
+    stopping: &AtomicBool,
+    state: &Arc<Mutex<State>>,
+    tickets: Sender<u32>,
+) {
+    for stream in listener.incoming() {
+        if stopping.load(Ordering::SeqCst) {
+            break;
+        }
+        let Ok(stream) = stream else {
+            continue;
+        };
+
+        let state = Arc::clone(state);
+        let tickets = tickets.clone();
+        thread::spawn(move || {
+            let _ = handle_connection(stream, &state, &tickets);
+        });
+    }
+}
+
+/// The kitchen's loop: take each order on the stove through to the table.
+fn cook(state: &Mutex<State>, tickets: Receiver<u32>) {
+    for id in tickets {
+        let Ok(mut state) = state.lock() else {
+            break;
+        };
+        if let Some(order) = state.orders.get_mut(&id) {
+            crate::deliver_order(order).expect("a cooking order can be delivered");
+            serving::serve_order(order).expect("a delivered order is ready");
+        }
+    }
+}
+
+/// A parsed request line and body. Headers other than `Content-Length` are ignored.
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+/// A status code and the JSON to send with it.
+struct Response {
+    status: u16,
+    body: Json,
+}
+
+impl Response {
+    fn ok(body: Json) -> Response {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl ToString) -> Response {
+        Response {
+            status,
+            body: Json::object([("error", Json::from(message.to_string()))]),
+        }
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    state: &Mutex<State>,
+    tickets: &Sender<u32>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let response = match read_request(&mut stream) {
+        Ok(request) => route(&request, state, tickets),
+        Err(error) => Response::error(400, error),
+    };
+
+    let body = response.body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        response.status,
+        reason_phrase(response.status),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn read_request(stream: &mut TcpStream) -> Result<Request, String> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|error| error.to_string())?;
+
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(String::from("malformed request line"));
+    };
+    let (method, path) = (String::from(method), String::from(path));
+
+    let mut length = 0;
+    loop {
+        line.clear();
+        reader.read_line(&mut line).map_err(|error| error.to_string())?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().map_err(|_| "invalid Content-Length")?;
+            }
+        }
+    }
+
+    if length > MAX_BODY {
+        return Err(format!("the body may be at most {MAX_BODY} bytes"));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(|error| error.to_string())?;
+    let body = String::from_utf8(body).map_err(|_| "the body is not UTF-8")?;
+
+    Ok(Request { method, path, body })
+}
+
+fn route(request: &Request, state: &Mutex<State>, tickets: &Sender<u32>) -> Response {
+    let Ok(mut state) = state.lock() else {
+        return Response::error(500, "the server is in a bad state");
+    };
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["menu"]) => Response::ok(menu_json(&state.config)),
+        ("POST", ["orders"]) => with_body(request, |body| place_order(&mut state, tickets, body)),
+        ("GET", ["orders", id]) => match find_order(&state, id) {
+            Ok(order) => Response::ok(order_json(order)),
+            Err(response) => response,
+        },
+        ("POST", ["orders", id, "payment"]) => {
+            with_body(request, |body| take_payment(&mut state, id, body))
+        }
+        (_, ["menu"] | ["orders"] | ["orders", _] | ["orders", _, "payment"]) => {
+            Response::error(405, format!("{} is not allowed on {}", request.method, request.path))
+        }
+        _ => Response::error(404, format!("nothing at {}", request.path)),
+    }
+}
+
+/// Parses the request body before passing it on.
+fn with_body(request: &Request, handler: impl FnOnce(&Json) -> Response) -> Response {
+    match Json::parse(&request.body) {
+        Ok(body) => handler(&body),
+        Err(error) => Response::error(400, format!("invalid JSON: {error}")),
+    }
+}
+
+fn find_order<'a>(state: &'a State, id: &str) -> Result<&'a Order, Response> {
+    id.parse()
+        .ok()
+        .and_then(|id| state.orders.get(&id))
+        .ok_or_else(|| Response::error(404, format!("no order #{id}")))
+}
+
+fn place_order(state: &mut State, tickets: &Sender<u32>, body: &Json) -> Response {
+    let Some(table) = body.get("table").and_then(Json::as_u64) else {
+        return Response::error(400, "'table' must be a table number");
+    };
+    let Ok(table) = u32::try_from(table) else {
+        return Response::error(400, format!("there is no table {table}"));
+    };
+    let Some(names) = body
+        .get("items")
+        .and_then(Json::as_array)
+        .and_then(|items| items.iter().map(Json::as_str).collect::<Option<Vec<&str>>>())
+    else {
+        return Response::error(400, "'items' must be a list of menu item names");
+    };
+    if names.is_empty() {
+        return Response::error(400, "an order needs at least one item");
+    }
+    if let Err(error) = state.config.menu.order(&names, state.config.season) {
+        return Response::error(400, error);
+    }
+
+    // Send the ticket first so a closed kitchen leaves no order behind. The kitchen can't look
+    // it up early: that takes the state lock, which is held until this returns. If the pantry
+    // can't cover the order, the kitchen finds nothing under the ticket and the id goes unused.
+    let id = state.next_id;
+    if tickets.send(id).is_err() {
+        return Response::error(503, "the kitchen is closed");
+    }
+    state.next_id += 1;
+    let mut order = serving::take_order(id, table, names.into_iter().map(String::from).collect());
+    let Config {
+        menu, inventory, ..
+    } = &mut state.config;
+    if let Err(error) = back_of_house::cook_order(&mut order, inventory, menu) {
+        return Response::error(409, error);
+    }
+    let response = order_json(&order);
+    state.orders.insert(id, order);
+
+    Response {
+        status: 201,
+        body: response,
+    }
+}
+
+fn take_payment(state: &mut State, id: &str, body: &Json) -> Response {
+    let tendered = match body.get("tendered_cents").map(Json::as_u64) {
+        Some(Some(cents)) => Money::from_cents(cents),
+        _ => return Response::error(400, "'tendered_cents' must be an amount in cents"),
+    };
+    let tip = match body.get("tip_cents").map(Json::as_u64) {
+        None => Tip::None,
+        Some(Some(cents)) => Tip::Amount(Money::from_cents(cents)),
+        Some(None) => return Response::error(400, "'tip_cents' must be an amount in cents"),
+    };
+
+    let mut cash;
+    let mut card;
+    let mut gift_card;
+    let method: &mut dyn PaymentMethod = match body.get("method").and_then(Json::as_str) {
+        Some("cash") => {
+            cash = Cash::new(tendered);
+            &mut cash
+        }
+        Some("card") => {
+            let last_four = body.get("last_four").and_then(Json::as_str).unwrap_or("0000");
+            card = Card {
+                last_four: String::from(last_four),
+                available: tendered,
+            };
+            &mut card
+        }
+        Some("gift_card") => {
+            gift_card = GiftCard {
+                code: String::from(body.get("code").and_then(Json::as_str).unwrap_or("")),
+                balance: tendered,
+            };
+            &mut gift_card
+        }
+        _ => return Response::error(400, "'method' must be one of cash, card or gift_card"),
+    };
+
+    let State { config, orders, .. } = state;
+    let Some(order) = id.parse().ok().and_then(|id| orders.get_mut(&id)) else {
+        return Response::error(404, format!("no order #{id}"));
+    };
+    let bill = match Bill::for_order(order, &config.menu, config.tax_rate) {
+        Ok(bill) => bill.with_tip(tip),
+        Err(error) => return Response::error(500, error),
+    };
+
+    let total = bill.total();
+    match serving::take_payment(order, &bill, &mut [(method, total)]) {
+        Ok(receipt) => {
+            let change: Money = receipt.payments.iter().map(|payment| payment.change).sum();
+            Response::ok(Json::object([
+                ("id", Json::from(order.id())),
+                ("status", Json::from(order.status().to_string())),
+                ("subtotal_cents", Json::from(bill.subtotal().cents())),
+                ("tax_cents", Json::from(bill.tax().cents())),
+                ("tip_cents", Json::from(bill.tip().cents())),
+                ("total_cents", Json::from(total.cents())),
+                ("change_cents", Json::from(change.cents())),
+                ("receipt", Json::from(receipt.to_string())),
+            ]))
+        }
+        Err(BillingError::Order(error)) => Response::error(409, error),
+        Err(error @ BillingError::Declined { .. }) => Response::error(402, error),
+        Err(error) => Response::error(400, error),
+    }
+}
+
+fn menu_json(config: &Config) -> Json {
+    Json::Array(
+        config
+            .menu
+            .available_in(config.season)
+            .map(menu_item_json)
+            .collect(),
+    )
+}
+
+fn menu_item_json(item: &MenuItem) -> Json {
+    let allergens = item
+        .allergens
+        .iter()
+        .map(|allergen| Json::from(format!("{allergen:?}").to_lowercase()))
+        .collect();
+
+    Json::object([
+        ("name", Json::from(item.name.as_str())),
+        ("category", Json::from(format!("{:?}", item.category).to_lowercase())),
+        ("price_cents", Json::from(item.price)),
+        ("allergens", Json::Array(allergens)),
+    ])
+}
+
+fn order_json(order: &Order) -> Json {
+    let items = order.items.iter().map(|item| Json::from(item.as_str())).collect();
+    let history = order
+        .history()
+        .iter()
+        .map(|status| Json::from(status.to_string()))
+        .collect();
+
+    Json::object([
+        ("id", Json::from(order.id())),
+        ("table", Json::from(order.table)),
+        ("items", Json::Array(items)),
+        ("status", Json::from(order.status().to_string())),
+        ("history", Json::Array(history)),
+    ])
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        402 => "Payment Required",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
@@ -0,0 +1,168 @@
+//! The kitchen: what dishes are made of, the stock of ingredients and the cooks.
+//!
+//! `super` starts a path at the parent module, like `..` in a file system. Cooked orders are
+//! handed over with `super::deliver_order`, which lives at the crate root because the front of
+//! house uses it too; if both modules ever move together, that path doesn't have to change.
+//!
+//! A `pub` struct still keeps its fields private unless each is marked `pub`: customers choose a
+//! `Breakfast`'s toast but only read its seasonal fruit, and because of that private field a
+//! breakfast can only be built through a constructor such as `Breakfast::summer`. A `pub` enum,
+//! on the other hand, makes all of its variants public, so anyone can order an
+//! `Appetizer::Soup`.
+
+pub mod inventory;
+pub mod kitchen;
+
+use crate::back_of_house::inventory::{Ingredients, Inventory};
+use crate::back_of_house::kitchen::KitchenError;
+use crate::menu::{Menu, Season};
+use crate::order::{Order, OrderError, OrderStatus};
+
+/// A breakfast whose toast the customer picks, while the chef picks the fruit.
+pub struct Breakfast {
+    pub toast: String,
+    seasonal_fruit: String,
+}
+
+impl Breakfast {
+    /// Builds a breakfast with whatever fruit is in season.
+    pub fn for_season(toast: &str, season: Season) -> Breakfast {
+        let fruit = match season {
+            Season::Spring => "strawberries",
+            Season::Summer => "peaches",
+            Season::Autumn => "apples",
+            Season::Winter => "oranges",
+        };
+
+        Breakfast {
+            toast: String::from(toast),
+            seasonal_fruit: String::from(fruit),
+        }
+    }
+
+    pub fn summer(toast: &str) -> Breakfast {
+        Breakfast::for_season(toast, Season::Summer)
+    }
+
+    /// Customers may see the fruit, but they can't change it.
+    pub fn seasonal_fruit(&self) -> &str {
+        &self.seasonal_fruit
+    }
+
+    /// Name of the breakfast on the menu.
+    pub fn menu_name(&self) -> &'static str {
+        "Breakfast"
+    }
+
+    /// What goes into one breakfast: two slices of toast and the seasonal fruit.
+    pub fn ingredients(&self) -> Ingredients {
+        vec![
+            (String::from("bread"), 2),
+            (String::from("butter"), 10),
+            (self.seasonal_fruit.clone(), 1),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Appetizer {
+    Soup,
+    Salad,
+}
+
+impl Appetizer {
+    /// Name of the appetizer on the menu.
+    pub fn menu_name(self) -> &'static str {
+        match self {
+            Appetizer::Soup => "Soup",
+            Appetizer::Salad => "Salad",
+        }
+    }
+
+    /// What goes into one portion.
+    pub fn ingredients(self) -> Ingredients {
+        let ingredients: &[(&str, u32)] = match self {
+            Appetizer::Soup => &[("stock", 300), ("celery", 50), ("onions", 1)],
+            Appetizer::Salad => &[("lettuce", 1), ("tomatoes", 2), ("mustard", 10)],
+        };
+
+        ingredients
+            .iter()
+            .map(|&(name, amount)| (String::from(name), amount))
+            .collect()
+    }
+}
+
+/// Sends an order back to the kitchen with the right items and starts cooking it again.
+///
+/// Only orders that already left the kitchen (ready or served) can be fixed. The remake takes
+/// its ingredients out of `inventory` like any other order, and like any other order it is
+/// handed over with `deliver_order` once it's cooked.
+pub fn fix_incorrect_order(
+    order: &mut Order,
+    items: Vec<String>,
+    inventory: &mut Inventory,
+    menu: &mut Menu,
+) -> Result<Vec<(String, u32)>, KitchenError> {
+    start_cooking(order, Some(items), Some((inventory, menu)))
+}
+
+/// Starts cooking an order, taking its ingredients out of `inventory` and marking whatever
+/// can't be made any more as sold out on `menu`.
+///
+/// Returns the ingredients running low afterwards, with what's left of them. If anything is
+/// missing, nothing is taken and the order stays where it was.
+pub fn cook_order(
+    order: &mut Order,
+    inventory: &mut Inventory,
+    menu: &mut Menu,
+) -> Result<Vec<(String, u32)>, KitchenError> {
+    start_cooking(order, None, Some((inventory, menu)))
+}
+
+/// Starts cooking an order as it is, or with `remake`'s items if it was sent back.
+///
+/// With `stores`, the ingredients are taken out of the inventory and the menu is updated to
+/// match, whether or not there was enough. Both the order's status and the stock are checked
+/// before anything changes, so a refused order is left as it was.
+fn start_cooking(
+    order: &mut Order,
+    remake: Option<Vec<String>>,
+    stores: Option<(&mut Inventory, &mut Menu)>,
+) -> Result<Vec<(String, u32)>, KitchenError> {
+    let allowed = match remake {
+        Some(_) => matches!(order.status(), OrderStatus::Ready | OrderStatus::Served),
+        None => order.status().can_transition_to(OrderStatus::Cooking),
+    };
+    if !allowed {
+        return Err(KitchenError::Order(OrderError {
+            order: order.id(),
+            from: order.status(),
+            to: OrderStatus::Cooking,
+        }));
+    }
+
+    let mut low_stock = Vec::new();
+    if let Some((inventory, menu)) = stores {
+        let (items, reason) = match &remake {
+            Some(items) => (items, format!("remake of order #{}", order.id())),
+            None => (&order.items, format!("order #{}", order.id())),
+        };
+        let dishes: Vec<&str> = items.iter().map(String::as_str).collect();
+        let used = inventory.use_for(&dishes, &reason).map(|low_stock| {
+            low_stock
+                .into_iter()
+                .map(|(ingredient, left)| (String::from(ingredient), left))
+                .collect()
+        });
+
+        inventory.update_menu(menu);
+        low_stock = used?;
+    }
+
+    if let Some(items) = remake {
+        order.items = items;
+    }
+    order.advance(OrderStatus::Cooking)?;
+    Ok(low_stock)
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use super::{Appetizer, Breakfast};
+use crate::menu::{Menu, Season};
+
+/// Ingredients and amounts needed for one portion of a dish. Amounts are in each ingredient's
+/// own unit: grams, millilitres or pieces.
+pub type Ingredients = Vec<(String, u32)>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryError {
+    /// Nobody wrote down what goes into the dish.
+    NoIngredients(String),
+    OutOfStock {
+        ingredient: String,
+        needed: u32,
+        available: u32,
+    },
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InventoryError::NoIngredients(dish) => write!(f, "no ingredients listed for '{dish}'"),
+            InventoryError::OutOfStock {
+                ingredient,
+                needed,
+                available,
+            } => write!(f, "need {needed} of {ingredient} but only {available} left"),
+        }
+    }
+}
+
+impl Error for InventoryError {}
+
+/// A change to the stock of an ingredient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+    pub ingredient: String,
+    /// Positive for deliveries, negative for what the kitchen used.
+    pub change: i64,
+    pub reason: String,
+}
+
+/// The stock of every ingredient, and a ledger of how it got there.
+///
+/// Dishes are only cooked if every ingredient is in stock; an order either takes all it needs
+/// or nothing at all.
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    season: Season,
+    stock: HashMap<String, u32>,
+    /// Stock at or below which an ingredient is reported as running low.
+    reorder_levels: HashMap<String, u32>,
+    dishes: HashMap<String, Ingredients>,
+    ledger: Vec<LedgerEntry>,
+}
+
+impl Inventory {
+    /// An empty pantry that knows the ingredients of every dish on the default menu in
+    /// `season`.
+    pub fn new(season: Season) -> Inventory {
+        let mut inventory = Inventory {
+            season,
+            stock: HashMap::new(),
+            reorder_levels: HashMap::new(),
+            dishes: HashMap::new(),
+            ledger: Vec::new(),
+        };
+
+        let breakfast = Breakfast::for_season("", season);
+        inventory.set_ingredients(breakfast.menu_name(), breakfast.ingredients());
+        for appetizer in [Appetizer::Soup, Appetizer::Salad] {
+            inventory.set_ingredients(appetizer.menu_name(), appetizer.ingredients());
+        }
+
+        for (dish, ingredients) in [
+            ("Roast Chicken", &[("chicken", 1), ("potatoes", 300)][..]),
+            ("Grilled Fish", &[("fish", 1), ("lemons", 1)]),
+            ("Peach Cobbler", &[("peaches", 2), ("flour", 80), ("butter", 40)]),
+            ("Chocolate Tart", &[("chocolate", 60), ("flour", 60), ("eggs", 1)]),
+            ("Lemonade", &[("lemons", 2), ("sugar", 20)]),
+            ("Coffee", &[("coffee beans", 18)]),
+        ] {
+            let ingredients = ingredients
+                .iter()
+                .map(|&(name, amount)| (String::from(name), amount))
+                .collect();
+            inventory.set_ingredients(dish, ingredients);
+        }
+
+        inventory
+    }
+
+    /// A pantry with enough of everything for `portions` of every dish it knows.
+    pub fn stocked(season: Season, portions: u32) -> Inventory {
+        let mut inventory = Inventory::new(season);
+        let dishes: Vec<String> = inventory.dishes.keys().cloned().collect();
+        for dish in dishes {
+            inventory.restock_for(&dish, portions).expect("the dish is known");
+        }
+        inventory
+    }
+
+    pub fn season(&self) -> Season {
+        self.season
+    }
+
+    pub fn set_ingredients(&mut self, dish: &str, ingredients: Ingredients) {
+        self.dishes.insert(String::from(dish), ingredients);
+    }
+
+    pub fn ingredients(&self, dish: &str) -> Option<&Ingredients> {
+        self.dishes.get(dish)
+    }
+
+    pub fn stock(&self, ingredient: &str) -> u32 {
+        self.stock.get(ingredient).copied().unwrap_or(0)
+    }
+
+    pub fn ledger(&self) -> &[LedgerEntry] {
+        &self.ledger
+    }
+
+    /// Reports an ingredient as running low once its stock drops to `level` or below.
+    pub fn set_reorder_level(&mut self, ingredient: &str, level: u32) {
+        self.reorder_levels.insert(String::from(ingredient), level);
+    }
+
+    /// Records a delivery.
+    pub fn restock(&mut self, ingredient: &str, amount: u32) {
+        *self.stock.entry(String::from(ingredient)).or_default() += amount;
+        self.ledger.push(LedgerEntry {
+            ingredient: String::from(ingredient),
+            change: i64::from(amount),
+            reason: String::from("delivery"),
+        });
+    }
+
+    /// Records a delivery of everything `portions` more of `dish` need.
+    pub fn restock_for(&mut self, dish: &str, portions: u32) -> Result<(), InventoryError> {
+        let ingredients = self
+            .dishes
+            .get(dish)
+            .cloned()
+            .ok_or_else(|| InventoryError::NoIngredients(String::from(dish)))?;
+
+        for (ingredient, amount) in ingredients {
+            self.restock(&ingredient, amount * portions);
+        }
+        Ok(())
+    }
+
+    /// Ingredients at or below their reorder level, with what's left, sorted by name.
+    pub fn low_stock(&self) -> Vec<(&str, u32)> {
+        let mut low: Vec<(&str, u32)> = self
+            .reorder_levels
+            .iter()
+            .map(|(ingredient, &level)| (ingredient.as_str(), self.stock(ingredient), level))
+            .filter(|&(_, left, level)| left <= level)
+            .map(|(ingredient, left, _)| (ingredient, left))
+            .collect();
+        low.sort();
+        low
+    }
+
+    /// Whether there is enough of everything for one more portion of `dish`.
+    pub fn can_cook(&self, dish: &str) -> bool {
+        self.check(&[dish]).is_ok()
+    }
+
+    /// Takes the ingredients for `dishes` out of stock, writing `reason` in the ledger.
+    ///
+    /// Returns the ingredients that are running low afterwards. Nothing is taken if anything
+    /// is missing.
+    pub fn use_for(
+        &mut self,
+        dishes: &[&str],
+        reason: &str,
+    ) -> Result<Vec<(&str, u32)>, InventoryError> {
+        let needed = self.check(dishes)?;
+
+        for (ingredient, amount) in needed {
+            *self.stock.get_mut(&ingredient).expect("checked above") -= amount;
+            self.ledger.push(LedgerEntry {
+                ingredient,
+                change: -i64::from(amount),
+                reason: String::from(reason),
+            });
+        }
+
+        Ok(self.low_stock())
+    }
+
+    /// Marks every menu item the kitchen can't make right now as sold out, and everything else
+    /// as available again.
+    ///
+    /// Items without a recipe here are left as they are, since the inventory can't tell whether
+    /// they can be made. Cooking an order does this on its own; call it after a delivery to put
+    /// dishes back on.
+    pub fn update_menu(&self, menu: &mut Menu) {
+        let names: Vec<String> = menu
+            .items()
+            .iter()
+            .map(|item| item.name.clone())
+            .filter(|name| self.dishes.contains_key(name))
+            .collect();
+
+        for name in names {
+            menu.set_sold_out(&name, !self.can_cook(&name));
+        }
+    }
+
+    /// Adds up what `dishes` need and checks it's all in stock, returning the totals sorted by
+    /// ingredient.
+    fn check(&self, dishes: &[&str]) -> Result<Vec<(String, u32)>, InventoryError> {
+        let mut needed: HashMap<&str, u32> = HashMap::new();
+
+        for &dish in dishes {
+            let ingredients = self
+                .dishes
+                .get(dish)
+                .ok_or_else(|| InventoryError::NoIngredients(String::from(dish)))?;
+
+            for (ingredient, amount) in ingredients {
+                *needed.entry(ingredient).or_default() += amount;
+            }
+        }
+
+        let mut needed: Vec<(String, u32)> = needed
+            .into_iter()
+            .map(|(ingredient, amount)| (String::from(ingredient), amount))
+            .collect();
+        needed.sort();
+
+        for (ingredient, amount) in &needed {
+            let available = self.stock(ingredient);
+            if available < *amount {
+                return Err(InventoryError::OutOfStock {
+                    ingredient: ingredient.clone(),
+                    needed: *amount,
+                    available,
+                });
+            }
+        }
+
+        Ok(needed)
+    }
+}
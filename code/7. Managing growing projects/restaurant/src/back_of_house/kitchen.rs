@@ -0,0 +1,465 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use super::inventory::{Inventory, InventoryError};
+use crate::menu::Menu;
+use crate::order::{Order, OrderError};
+
+/// Where and how long a dish is cooked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recipe {
+    pub station: String,
+    /// Cook time in seconds of the simulated clock.
+    pub cook_time: u32,
+}
+
+/// A station of the kitchen, such as the grill, with the number of cooks working it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Station {
+    pub name: String,
+    pub cooks: u32,
+}
+
+impl Station {
+    pub fn new(name: &str, cooks: u32) -> Station {
+        Station {
+            name: String::from(name),
+            cooks,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum KitchenError {
+    Order(OrderError),
+    Inventory(InventoryError),
+    /// The kitchen has no recipe for a dish.
+    UnknownDish(String),
+    UnknownStation(String),
+    /// A recipe names a station the kitchen doesn't have, or one without cooks.
+    UnstaffedStation(String),
+    /// Everyone was sent home from a station that a ticket sent to the kitchen has dishes for.
+    StationBusy(String),
+}
+
+impl fmt::Display for KitchenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KitchenError::Order(error) => write!(f, "{error}"),
+            KitchenError::Inventory(error) => write!(f, "{error}"),
+            KitchenError::UnknownDish(dish) => {
+                write!(f, "the kitchen doesn't know how to cook '{dish}'")
+            }
+            KitchenError::UnknownStation(station) => write!(f, "there is no {station} station"),
+            KitchenError::UnstaffedStation(station) => {
+                write!(f, "nobody works the {station} station")
+            }
+            KitchenError::StationBusy(station) => {
+                write!(f, "the {station} station has dishes on the kitchen's tickets")
+            }
+        }
+    }
+}
+
+impl Error for KitchenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            KitchenError::Order(error) => Some(error),
+            KitchenError::Inventory(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<OrderError> for KitchenError {
+    fn from(error: OrderError) -> Self {
+        KitchenError::Order(error)
+    }
+}
+
+impl From<InventoryError> for KitchenError {
+    fn from(error: InventoryError) -> Self {
+        KitchenError::Inventory(error)
+    }
+}
+
+/// What the kitchen did with one ticket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TicketResult {
+    pub order: u32,
+    pub remake: bool,
+    pub submitted_at: u32,
+    pub finished_at: u32,
+}
+
+impl TicketResult {
+    /// Seconds from the ticket reaching the kitchen to its last dish being done.
+    pub fn ticket_time(&self) -> u32 {
+        self.finished_at - self.submitted_at
+    }
+}
+
+/// Summary of a simulation run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KitchenReport {
+    /// Tickets in the order they were finished.
+    pub tickets: Vec<TicketResult>,
+    pub remakes: usize,
+    /// Seconds from the first ticket arriving to the last one finishing.
+    pub elapsed: u32,
+    /// Finished tickets per hour of simulated time.
+    pub throughput: f64,
+    /// Average ticket time, in seconds.
+    pub average_ticket_time: f64,
+    /// Share of each station's cook time that was spent cooking, between 0 and 1.
+    pub utilization: HashMap<String, f64>,
+}
+
+/// A dish waiting for, or being cooked at, a station.
+#[derive(Debug, Clone)]
+struct Job {
+    ticket: usize,
+    cook_time: u32,
+}
+
+/// Something that happens at a point of the simulated clock. The derived ordering makes
+/// arrivals sort before completions at the same instant.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    TicketArrives { ticket: usize },
+    DishDone { station: usize, ticket: usize },
+}
+
+#[derive(Debug, Clone)]
+struct Ticket {
+    order: u32,
+    dishes: Vec<String>,
+    remake: bool,
+    submitted_at: u32,
+}
+
+/// A discrete-event simulation of the kitchen.
+///
+/// Tickets wait in a queue at each station and are cooked first come, first served, except for
+/// remakes, which jump to the front. Time only moves from one event to the next, so the same
+/// tickets always produce the same report.
+///
+/// A kitchen with an inventory takes each ticket's ingredients out of stock when it's queued,
+/// refuses tickets it doesn't have the ingredients for, and marks whatever it can no longer make
+/// as sold out on its menu.
+#[derive(Debug, Clone)]
+pub struct Kitchen {
+    stations: Vec<Station>,
+    recipes: HashMap<String, Recipe>,
+    tickets: Vec<Ticket>,
+    inventory: Option<Inventory>,
+    /// Where dishes the inventory runs out of are sold out.
+    menu: Menu,
+}
+
+impl Kitchen {
+    pub fn new(stations: Vec<Station>) -> Kitchen {
+        Kitchen {
+            stations,
+            recipes: HashMap::new(),
+            tickets: Vec::new(),
+            inventory: None,
+            menu: Menu::default(),
+        }
+    }
+
+    pub fn with_inventory(mut self, inventory: Inventory) -> Kitchen {
+        self.inventory = Some(inventory);
+        self
+    }
+
+    pub fn inventory(&self) -> Option<&Inventory> {
+        self.inventory.as_ref()
+    }
+
+    pub fn inventory_mut(&mut self) -> Option<&mut Inventory> {
+        self.inventory.as_mut()
+    }
+
+    /// Sells out dishes on `menu` instead of the default menu.
+    pub fn with_menu(mut self, menu: Menu) -> Kitchen {
+        self.menu = menu;
+        self
+    }
+
+    pub fn menu(&self) -> &Menu {
+        &self.menu
+    }
+
+    /// A kitchen that can cook everything on the default menu, with one cook per station.
+    pub fn standard() -> Kitchen {
+        let mut kitchen = Kitchen::new(vec![
+            Station::new("stove", 1),
+            Station::new("grill", 1),
+            Station::new("cold", 1),
+            Station::new("bar", 1),
+        ]);
+
+        for (dish, station, cook_time) in [
+            ("Soup", "stove", 240),
+            ("Breakfast", "stove", 480),
+            ("Roast Chicken", "grill", 900),
+            ("Grilled Fish", "grill", 720),
+            ("Salad", "cold", 300),
+            ("Peach Cobbler", "cold", 180),
+            ("Chocolate Tart", "cold", 120),
+            ("Lemonade", "bar", 60),
+            ("Coffee", "bar", 90),
+        ] {
+            kitchen.add_recipe(dish, station, cook_time);
+        }
+
+        kitchen
+    }
+
+    pub fn add_recipe(&mut self, dish: &str, station: &str, cook_time: u32) {
+        self.recipes.insert(
+            String::from(dish),
+            Recipe {
+                station: String::from(station),
+                cook_time,
+            },
+        );
+    }
+
+    /// Changes how many cooks work a station.
+    ///
+    /// A station can't be left without cooks once any ticket sent to the kitchen has a dish for
+    /// it, or that dish would never be cooked. Tickets stay with the kitchen after `run`, which
+    /// cooks all of them every time, so this holds even after the dish has been cooked.
+    pub fn set_cooks(&mut self, station: &str, cooks: u32) -> Result<(), KitchenError> {
+        let index = self
+            .stations
+            .iter()
+            .position(|known| known.name == station)
+            .ok_or_else(|| KitchenError::UnknownStation(String::from(station)))?;
+
+        let busy = self
+            .tickets
+            .iter()
+            .flat_map(|ticket| &ticket.dishes)
+            .any(|dish| self.recipe_for(dish).0 == index);
+        if cooks == 0 && busy {
+            return Err(KitchenError::StationBusy(String::from(station)));
+        }
+
+        self.stations[index].cooks = cooks;
+        Ok(())
+    }
+
+    /// Sends an order's ticket to the kitchen at `at` seconds and starts cooking it.
+    ///
+    /// Returns the ingredients running low afterwards, with what's left of them.
+    pub fn cook_order(
+        &mut self,
+        order: &mut Order,
+        at: u32,
+    ) -> Result<Vec<(String, u32)>, KitchenError> {
+        self.check_dishes(&order.items)?;
+        let stores = self.inventory.as_mut().map(|inventory| (inventory, &mut self.menu));
+        let low_stock = super::start_cooking(order, None, stores)?;
+        self.queue(order, false, at);
+        Ok(low_stock)
+    }
+
+    /// Sends an order back with the right items as a remake ticket, which is cooked ahead of
+    /// regular tickets.
+    pub fn fix_incorrect_order(
+        &mut self,
+        order: &mut Order,
+        items: Vec<String>,
+        at: u32,
+    ) -> Result<Vec<(String, u32)>, KitchenError> {
+        self.check_dishes(&items)?;
+        let stores = self.inventory.as_mut().map(|inventory| (inventory, &mut self.menu));
+        let low_stock = super::start_cooking(order, Some(items), stores)?;
+        self.queue(order, true, at);
+        Ok(low_stock)
+    }
+
+    /// Cooks every ticket sent to the kitchen so far and reports how the kitchen did.
+    ///
+    /// The tickets are kept, so running again after changing the staffing replays the same
+    /// service.
+    pub fn run(&self) -> KitchenReport {
+        let mut events = BinaryHeap::new();
+        let mut queues: Vec<VecDeque<Job>> = vec![VecDeque::new(); self.stations.len()];
+        let mut idle_cooks: Vec<u32> = self.stations.iter().map(|station| station.cooks).collect();
+        let mut busy_time: Vec<u32> = vec![0; self.stations.len()];
+        let mut dishes_left: Vec<usize> =
+            self.tickets.iter().map(|ticket| ticket.dishes.len()).collect();
+        let mut results = Vec::new();
+
+        for (index, ticket) in self.tickets.iter().enumerate() {
+            // The ticket index breaks ties, so simultaneous tickets keep their submission order
+            events.push(Reverse((ticket.submitted_at, Event::TicketArrives { ticket: index })));
+        }
+
+        let mut now = 0;
+        while let Some(Reverse((time, event))) = events.pop() {
+            now = time;
+
+            let touched = match event {
+                Event::TicketArrives { ticket } => {
+                    let remake = self.tickets[ticket].remake;
+                    let mut touched = Vec::new();
+
+                    // With nothing to cook, the ticket is done as soon as it arrives
+                    if dishes_left[ticket] == 0 {
+                        results.push(self.result(ticket, now));
+                    }
+
+                    for dish in &self.tickets[ticket].dishes {
+                        let (station, cook_time) = self.recipe_for(dish);
+                        let queue = &mut queues[station];
+                        let job = Job { ticket, cook_time };
+
+                        if remake {
+                            // Behind earlier remakes, ahead of everything else
+                            let position = queue
+                                .iter()
+                                .position(|job| !self.tickets[job.ticket].remake)
+                                .unwrap_or(queue.len());
+                            queue.insert(position, job);
+                        } else {
+                            queue.push_back(job);
+                        }
+                        touched.push(station);
+                    }
+                    touched
+                }
+                Event::DishDone { station, ticket } => {
+                    idle_cooks[station] += 1;
+                    dishes_left[ticket] -= 1;
+
+                    if dishes_left[ticket] == 0 {
+                        results.push(self.result(ticket, now));
+                    }
+                    vec![station]
+                }
+            };
+
+            // Put any idle cooks at the touched stations to work
+            for station in touched {
+                while idle_cooks[station] > 0 {
+                    let Some(job) = queues[station].pop_front() else {
+                        break;
+                    };
+                    idle_cooks[station] -= 1;
+                    busy_time[station] += job.cook_time;
+                    events.push(Reverse((
+                        now + job.cook_time,
+                        Event::DishDone {
+                            station,
+                            ticket: job.ticket,
+                        },
+                    )));
+                }
+            }
+        }
+
+        self.report(results, now, &busy_time)
+    }
+
+    fn result(&self, ticket: usize, finished_at: u32) -> TicketResult {
+        let ticket = &self.tickets[ticket];
+        TicketResult {
+            order: ticket.order,
+            remake: ticket.remake,
+            submitted_at: ticket.submitted_at,
+            finished_at,
+        }
+    }
+
+    fn report(&self, tickets: Vec<TicketResult>, end: u32, busy_time: &[u32]) -> KitchenReport {
+        let start = self
+            .tickets
+            .iter()
+            .map(|ticket| ticket.submitted_at)
+            .min()
+            .unwrap_or(0);
+        let elapsed = end.saturating_sub(start);
+
+        let throughput = if elapsed == 0 {
+            0.0
+        } else {
+            tickets.len() as f64 * 3_600.0 / f64::from(elapsed)
+        };
+        let average_ticket_time = if tickets.is_empty() {
+            0.0
+        } else {
+            let total: u32 = tickets.iter().map(TicketResult::ticket_time).sum();
+            f64::from(total) / tickets.len() as f64
+        };
+        let utilization = self
+            .stations
+            .iter()
+            .zip(busy_time)
+            .map(|(station, &busy)| {
+                let available = f64::from(elapsed) * f64::from(station.cooks);
+                let share = if available == 0.0 {
+                    0.0
+                } else {
+                    f64::from(busy) / available
+                };
+                (station.name.clone(), share)
+            })
+            .collect();
+
+        KitchenReport {
+            remakes: tickets.iter().filter(|ticket| ticket.remake).count(),
+            tickets,
+            elapsed,
+            throughput,
+            average_ticket_time,
+            utilization,
+        }
+    }
+
+    /// The station index and cook time of a dish that passed `check_dishes`.
+    fn recipe_for(&self, dish: &str) -> (usize, u32) {
+        let recipe = &self.recipes[dish];
+        let station = self
+            .stations
+            .iter()
+            .position(|station| station.name == recipe.station)
+            .expect("dishes are checked when their ticket is queued");
+
+        (station, recipe.cook_time)
+    }
+
+    /// Makes sure every dish has a recipe at a staffed station.
+    fn check_dishes(&self, dishes: &[String]) -> Result<(), KitchenError> {
+        for dish in dishes {
+            let recipe = self
+                .recipes
+                .get(dish)
+                .ok_or_else(|| KitchenError::UnknownDish(dish.clone()))?;
+
+            if !self
+                .stations
+                .iter()
+                .any(|station| station.name == recipe.station && station.cooks > 0)
+            {
+                return Err(KitchenError::UnstaffedStation(recipe.station.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn queue(&mut self, order: &Order, remake: bool, at: u32) {
+        self.tickets.push(Ticket {
+            order: order.id(),
+            dishes: order.items.clone(),
+            remake,
+            submitted_at: at,
+        });
+    }
+}
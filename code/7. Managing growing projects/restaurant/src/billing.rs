@@ -0,0 +1,444 @@
+use std::error::Error;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+use crate::menu::{Menu, MenuError};
+use crate::order::{Order, OrderError};
+
+/// An amount of money in cents. Keeping minor units as integers means nothing is lost to
+/// floating-point rounding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Money(u64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub fn from_cents(cents: u64) -> Money {
+        Money(cents)
+    }
+
+    pub fn cents(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the given percentage of the amount, where `basis_points` is hundredths of a
+    /// percent (825 is 8.25%). Half a cent rounds up.
+    pub fn percent(self, basis_points: u32) -> Money {
+        Money((self.0 * u64::from(basis_points) + 5_000) / 10_000)
+    }
+
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        self.0.checked_sub(other.0).map(Money)
+    }
+
+    /// Splits the amount in parts proportional to `weights`, handing out the leftover cents to
+    /// the largest remainders so the parts always add up to the whole.
+    ///
+    /// With all weights zero, the amount is split evenly instead.
+    pub fn allocate(self, weights: &[u64]) -> Vec<Money> {
+        if weights.is_empty() {
+            return Vec::new();
+        }
+
+        let weights: Vec<u64> = if weights.iter().all(|&weight| weight == 0) {
+            vec![1; weights.len()]
+        } else {
+            weights.to_vec()
+        };
+        let total_weight: u64 = weights.iter().sum();
+
+        let mut parts: Vec<u64> = weights
+            .iter()
+            .map(|weight| self.0 * weight / total_weight)
+            .collect();
+        let mut leftover = self.0 - parts.iter().sum::<u64>();
+
+        // Largest remainder first; earlier guests win ties
+        let mut by_remainder: Vec<usize> = (0..weights.len()).collect();
+        by_remainder
+            .sort_by_key(|&index| std::cmp::Reverse(self.0 * weights[index] % total_weight));
+
+        for index in by_remainder {
+            if leftover == 0 {
+                break;
+            }
+            parts[index] += 1;
+            leftover -= 1;
+        }
+
+        parts.into_iter().map(Money).collect()
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "${}.{:02}", self.0 / 100, self.0 % 100)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+/// How the guests want to tip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tip {
+    None,
+    /// A percentage of the subtotal, in basis points.
+    Percent(u32),
+    Amount(Money),
+}
+
+/// One priced item on a bill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BillLine {
+    pub name: String,
+    pub price: Money,
+}
+
+/// The itemized bill of an order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bill {
+    pub order: u32,
+    pub table: u32,
+    pub lines: Vec<BillLine>,
+    /// Sales tax in basis points, applied to the subtotal.
+    pub tax_rate: u32,
+    pub tip: Tip,
+}
+
+impl Bill {
+    /// Prices every item of the order using the menu.
+    pub fn for_order(order: &Order, menu: &Menu, tax_rate: u32) -> Result<Bill, BillingError> {
+        let lines = order
+            .items
+            .iter()
+            .map(|name| {
+                let item = menu
+                    .item(name)
+                    .ok_or_else(|| MenuError::UnknownItem(name.clone()))?;
+
+                Ok(BillLine {
+                    name: item.name.clone(),
+                    price: Money::from_cents(u64::from(item.price)),
+                })
+            })
+            .collect::<Result<Vec<_>, MenuError>>()?;
+
+        Ok(Bill {
+            order: order.id(),
+            table: order.table,
+            lines,
+            tax_rate,
+            tip: Tip::None,
+        })
+    }
+
+    pub fn with_tip(mut self, tip: Tip) -> Bill {
+        self.tip = tip;
+        self
+    }
+
+    pub fn subtotal(&self) -> Money {
+        self.lines.iter().map(|line| line.price).sum()
+    }
+
+    pub fn tax(&self) -> Money {
+        self.subtotal().percent(self.tax_rate)
+    }
+
+    pub fn tip(&self) -> Money {
+        match self.tip {
+            Tip::None => Money::ZERO,
+            Tip::Percent(basis_points) => self.subtotal().percent(basis_points),
+            Tip::Amount(amount) => amount,
+        }
+    }
+
+    pub fn total(&self) -> Money {
+        self.subtotal() + self.tax() + self.tip()
+    }
+
+    /// Splits the total evenly between `guests`, to the cent.
+    pub fn split_evenly(&self, guests: usize) -> Vec<Money> {
+        self.total().allocate(&vec![1; guests])
+    }
+
+    /// Splits the total by who had what. Each guest lists the indices of their bill lines and
+    /// pays tax and tip in proportion to their share of the subtotal.
+    ///
+    /// Returns `None` if a line is missing, claimed twice or doesn't exist.
+    pub fn split_by_items(&self, guests: &[Vec<usize>]) -> Option<Vec<Money>> {
+        let mut claimed = vec![false; self.lines.len()];
+        let mut subtotals = Vec::with_capacity(guests.len());
+
+        for lines in guests {
+            let mut subtotal = Money::ZERO;
+            for &index in lines {
+                if std::mem::replace(claimed.get_mut(index)?, true) {
+                    return None;
+                }
+                subtotal += self.lines[index].price;
+            }
+            subtotals.push(subtotal.cents());
+        }
+
+        if claimed.contains(&false) {
+            return None;
+        }
+        Some(self.total().allocate(&subtotals))
+    }
+}
+
+/// A way of paying, such as cash or a card.
+pub trait PaymentMethod {
+    /// Short name printed on the receipt.
+    fn description(&self) -> String;
+
+    /// Takes `amount`, returning the change handed back, or why the payment was declined.
+    fn charge(&mut self, amount: Money) -> Result<Money, String>;
+
+    /// Gives back an amount charged earlier, when another part of the payment failed.
+    fn refund(&mut self, amount: Money);
+}
+
+/// Notes and coins handed over by a guest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cash {
+    pub tendered: Money,
+    /// What the last charge took, so a refund hands back exactly the notes and coins tendered.
+    taken: Money,
+}
+
+impl Cash {
+    pub fn new(tendered: Money) -> Cash {
+        Cash {
+            tendered,
+            taken: Money::ZERO,
+        }
+    }
+}
+
+impl PaymentMethod for Cash {
+    fn description(&self) -> String {
+        String::from("Cash")
+    }
+
+    fn charge(&mut self, amount: Money) -> Result<Money, String> {
+        let change = self
+            .tendered
+            .checked_sub(amount)
+            .ok_or_else(|| format!("{} is not enough to pay {}", self.tendered, amount))?;
+        self.taken = std::mem::take(&mut self.tendered);
+        Ok(change)
+    }
+
+    /// Hands back everything that was tendered, not just `amount`: the change was never given
+    /// out if the payment is rolled back.
+    fn refund(&mut self, _amount: Money) {
+        self.tendered += std::mem::take(&mut self.taken);
+    }
+}
+
+/// A credit or debit card with some spending limit left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub last_four: String,
+    pub available: Money,
+}
+
+impl PaymentMethod for Card {
+    fn description(&self) -> String {
+        format!("Card ****{}", self.last_four)
+    }
+
+    fn charge(&mut self, amount: Money) -> Result<Money, String> {
+        self.available = self
+            .available
+            .checked_sub(amount)
+            .ok_or_else(|| String::from("insufficient funds"))?;
+        Ok(Money::ZERO)
+    }
+
+    fn refund(&mut self, amount: Money) {
+        self.available += amount;
+    }
+}
+
+/// A prepaid gift card issued by the restaurant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiftCard {
+    pub code: String,
+    pub balance: Money,
+}
+
+impl PaymentMethod for GiftCard {
+    fn description(&self) -> String {
+        format!("Gift card {}", self.code)
+    }
+
+    fn charge(&mut self, amount: Money) -> Result<Money, String> {
+        self.balance = self
+            .balance
+            .checked_sub(amount)
+            .ok_or_else(|| format!("only {} left on the card", self.balance))?;
+        Ok(Money::ZERO)
+    }
+
+    fn refund(&mut self, amount: Money) {
+        self.balance += amount;
+    }
+}
+
+#[derive(Debug)]
+pub enum BillingError {
+    Order(OrderError),
+    Menu(MenuError),
+    /// The payments don't add up to the bill.
+    AmountMismatch { expected: Money, paid: Money },
+    Declined { method: String, reason: String },
+    /// The bill belongs to a different order than the one being paid.
+    WrongBill { order: u32, bill: u32 },
+}
+
+impl fmt::Display for BillingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BillingError::Order(error) => write!(f, "{error}"),
+            BillingError::Menu(error) => write!(f, "{error}"),
+            BillingError::AmountMismatch { expected, paid } => {
+                write!(f, "the bill is {expected} but {paid} was offered")
+            }
+            BillingError::Declined { method, reason } => write!(f, "{method} declined: {reason}"),
+            BillingError::WrongBill { order, bill } => {
+                write!(f, "the bill is for order #{bill}, not order #{order}")
+            }
+        }
+    }
+}
+
+impl Error for BillingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BillingError::Order(error) => Some(error),
+            BillingError::Menu(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<OrderError> for BillingError {
+    fn from(error: OrderError) -> Self {
+        BillingError::Order(error)
+    }
+}
+
+impl From<MenuError> for BillingError {
+    fn from(error: MenuError) -> Self {
+        BillingError::Menu(error)
+    }
+}
+
+/// A payment as it appears on the receipt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentLine {
+    pub method: String,
+    pub amount: Money,
+    pub change: Money,
+}
+
+/// Proof of payment for a bill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Receipt {
+    pub bill: Bill,
+    pub payments: Vec<PaymentLine>,
+}
+
+impl fmt::Display for Receipt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const WIDTH: usize = 32;
+        let row = |f: &mut fmt::Formatter, label: &str, amount: Money| {
+            let amount = amount.to_string();
+            writeln!(f, "{label:<width$}{amount}", width = WIDTH - amount.len())
+        };
+
+        writeln!(f, "Order #{} - Table {}", self.bill.order, self.bill.table)?;
+        writeln!(f, "{}", "-".repeat(WIDTH))?;
+        for line in &self.bill.lines {
+            row(f, &line.name, line.price)?;
+        }
+        writeln!(f, "{}", "-".repeat(WIDTH))?;
+        row(f, "Subtotal", self.bill.subtotal())?;
+        row(f, "Tax", self.bill.tax())?;
+        row(f, "Tip", self.bill.tip())?;
+        row(f, "Total", self.bill.total())?;
+        writeln!(f, "{}", "-".repeat(WIDTH))?;
+        for payment in &self.payments {
+            row(f, &payment.method, payment.amount)?;
+            if payment.change > Money::ZERO {
+                row(f, "  Change", payment.change)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Charges each payment method its amount and settles the bill.
+///
+/// The amounts must add up to the bill's total. If any method declines, the ones already
+/// charged are refunded and nothing changes.
+pub fn settle(
+    bill: &Bill,
+    payments: &mut [(&mut dyn PaymentMethod, Money)],
+) -> Result<Receipt, BillingError> {
+    let paid: Money = payments.iter().map(|(_, amount)| *amount).sum();
+    if paid != bill.total() {
+        return Err(BillingError::AmountMismatch {
+            expected: bill.total(),
+            paid,
+        });
+    }
+
+    let mut lines = Vec::with_capacity(payments.len());
+
+    for index in 0..payments.len() {
+        let (method, amount) = &mut payments[index];
+
+        match method.charge(*amount) {
+            Ok(change) => lines.push(PaymentLine {
+                method: method.description(),
+                amount: *amount,
+                change,
+            }),
+            Err(reason) => {
+                let method = method.description();
+                for (charged, amount) in payments[..index].iter_mut() {
+                    charged.refund(*amount);
+                }
+                return Err(BillingError::Declined { method, reason });
+            }
+        }
+    }
+
+    Ok(Receipt {
+        bill: bill.clone(),
+        payments: lines,
+    })
+}
@@ -0,0 +1,14 @@
+//! Everything the guests see: the host, reservations and the waiters.
+//!
+//! Items are private to their module by default. A parent can't reach into the private items of
+//! its children, but a child can use anything in its ancestors, so implementation details stay
+//! hidden while still being able to use the context they're defined in. Marking a module `pub`
+//! only lets its parent refer to it; its contents stay private until they are made `pub` too,
+//! which is why `hosting`, `reservations` and `serving` mark both the modules and the items the
+//! rest of the crate uses.
+//!
+//! This module itself is private to the crate root; its children are re-exported from there.
+
+pub mod hosting;
+pub mod reservations;
+pub mod serving;
@@ -0,0 +1,255 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+
+/// A group of guests waiting for a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Party {
+    pub name: String,
+    pub size: u32,
+    /// Higher goes first under `SeatingStrategy::Priority`; 0 for regular walk-ins.
+    pub priority: u8,
+    /// Minute of the simulated clock at which the party joined the waitlist.
+    pub arrived_at: u32,
+}
+
+impl Party {
+    pub fn new(name: &str, size: u32, arrived_at: u32) -> Party {
+        Party {
+            name: String::from(name),
+            size,
+            priority: 0,
+            arrived_at,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: u8) -> Party {
+        self.priority = priority;
+        self
+    }
+}
+
+/// A table of the dining room.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    pub number: u32,
+    pub seats: u32,
+    /// Minute at which the table is expected to be free again, `None` while it is free.
+    busy_until: Option<u32>,
+}
+
+impl Table {
+    pub fn new(number: u32, seats: u32) -> Table {
+        Table {
+            number,
+            seats,
+            busy_until: None,
+        }
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.busy_until.is_none()
+    }
+}
+
+/// How the host picks the next party to seat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeatingStrategy {
+    /// Strictly in order of arrival: nobody is seated before the party at the head of the line.
+    Fifo,
+    /// Any party may be seated, choosing the pairing that leaves the fewest empty seats.
+    BestFit,
+    /// Higher-priority parties first, then by arrival; parties that don't fit yet are skipped.
+    Priority,
+}
+
+/// A party being shown to a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seating {
+    pub party: Party,
+    pub table: u32,
+    pub seated_at: u32,
+}
+
+/// Errors the host can run into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostingError {
+    EmptyParty,
+    /// No table in the restaurant has enough seats, so the party would wait forever.
+    NoTableLargeEnough { size: u32 },
+    UnknownTable(u32),
+    TableAlreadyFree(u32),
+}
+
+impl fmt::Display for HostingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HostingError::EmptyParty => write!(f, "a party needs at least one guest"),
+            HostingError::NoTableLargeEnough { size } => {
+                write!(f, "no table can seat a party of {size}")
+            }
+            HostingError::UnknownTable(number) => write!(f, "there is no table {number}"),
+            HostingError::TableAlreadyFree(number) => write!(f, "table {number} is already free"),
+        }
+    }
+}
+
+impl Error for HostingError {}
+
+/// Keeps the waitlist and the table inventory, and decides who sits where.
+///
+/// Time is a simulated clock in minutes passed in by the caller, so seating decisions and wait
+/// estimates are fully deterministic.
+#[derive(Debug, Clone)]
+pub struct Host {
+    waitlist: VecDeque<Party>,
+    tables: Vec<Table>,
+    strategy: SeatingStrategy,
+    /// How long a party is expected to stay once seated, used to estimate waits.
+    pub dining_minutes: u32,
+}
+
+impl Host {
+    pub fn new(tables: Vec<Table>, strategy: SeatingStrategy) -> Host {
+        Host {
+            waitlist: VecDeque::new(),
+            tables,
+            strategy,
+            dining_minutes: 45,
+        }
+    }
+
+    pub fn waitlist(&self) -> impl Iterator<Item = &Party> {
+        self.waitlist.iter()
+    }
+
+    pub fn tables(&self) -> &[Table] {
+        &self.tables
+    }
+
+    /// Puts a party at the end of the waitlist.
+    pub fn add_to_waitlist(&mut self, party: Party) -> Result<(), HostingError> {
+        if party.size == 0 {
+            return Err(HostingError::EmptyParty);
+        }
+        if !self.tables.iter().any(|table| table.seats >= party.size) {
+            return Err(HostingError::NoTableLargeEnough { size: party.size });
+        }
+
+        self.waitlist.push_back(party);
+        Ok(())
+    }
+
+    /// Seats as many waiting parties as the free tables and the strategy allow.
+    ///
+    /// Returns the seatings in the order they were made.
+    pub fn seat_at_table(&mut self, now: u32) -> Vec<Seating> {
+        let mut seatings = Vec::new();
+
+        while let Some((party_index, table_index)) = self.next_seating() {
+            let party = self.waitlist.remove(party_index).unwrap();
+            let table = &mut self.tables[table_index];
+            table.busy_until = Some(now + self.dining_minutes);
+
+            seatings.push(Seating {
+                party,
+                table: table.number,
+                seated_at: now,
+            });
+        }
+
+        seatings
+    }
+
+    /// Marks a table as free once its party has left.
+    pub fn free_table(&mut self, number: u32) -> Result<(), HostingError> {
+        let table = self
+            .tables
+            .iter_mut()
+            .find(|table| table.number == number)
+            .ok_or(HostingError::UnknownTable(number))?;
+
+        if table.busy_until.take().is_none() {
+            return Err(HostingError::TableAlreadyFree(number));
+        }
+        Ok(())
+    }
+
+    /// Estimates how many minutes from `now` the named party will wait for a table.
+    ///
+    /// Occupied tables are assumed to free up `dining_minutes` after they were taken, and every
+    /// party ahead is assumed to stay that long as well. Returns `None` for unknown parties.
+    pub fn estimate_wait(&self, name: &str, now: u32) -> Option<u32> {
+        let mut free_at: Vec<u32> = self
+            .tables
+            .iter()
+            .map(|table| table.busy_until.unwrap_or(now).max(now))
+            .collect();
+
+        for party in self.seating_order() {
+            // The fitting table that frees up first, preferring the smallest one on a tie
+            let (index, _) = self
+                .tables
+                .iter()
+                .enumerate()
+                .filter(|(_, table)| table.seats >= party.size)
+                .min_by_key(|&(index, table)| (free_at[index], table.seats))?;
+
+            if party.name == name {
+                return Some(free_at[index] - now);
+            }
+            free_at[index] += self.dining_minutes;
+        }
+
+        None
+    }
+
+    /// The waiting parties in the order the strategy would consider them.
+    fn seating_order(&self) -> Vec<&Party> {
+        let mut parties: Vec<&Party> = self.waitlist.iter().collect();
+
+        if self.strategy == SeatingStrategy::Priority {
+            // A stable sort keeps arrival order among parties of the same priority
+            parties.sort_by_key(|party| std::cmp::Reverse(party.priority));
+        }
+
+        parties
+    }
+
+    /// Picks the next party to seat and its table, as indices into the waitlist and tables.
+    fn next_seating(&self) -> Option<(usize, usize)> {
+        let smallest_free_table = |size: u32| {
+            self.tables
+                .iter()
+                .enumerate()
+                .filter(|(_, table)| table.is_free() && table.seats >= size)
+                .min_by_key(|(_, table)| table.seats)
+                .map(|(index, _)| index)
+        };
+
+        match self.strategy {
+            SeatingStrategy::Fifo => {
+                let head = self.waitlist.front()?;
+                smallest_free_table(head.size).map(|table| (0, table))
+            }
+            SeatingStrategy::BestFit => self
+                .waitlist
+                .iter()
+                .enumerate()
+                .filter_map(|(index, party)| {
+                    smallest_free_table(party.size).map(|table| (index, table))
+                })
+                .min_by_key(|&(index, table)| {
+                    (self.tables[table].seats - self.waitlist[index].size, index)
+                }),
+            SeatingStrategy::Priority => {
+                let party = self
+                    .seating_order()
+                    .into_iter()
+                    .find(|party| smallest_free_table(party.size).is_some())?;
+                let index = self.waitlist.iter().position(|waiting| waiting == party)?;
+                smallest_free_table(party.size).map(|table| (index, table))
+            }
+        }
+    }
+}
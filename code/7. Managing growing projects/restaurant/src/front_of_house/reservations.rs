@@ -0,0 +1,568 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::hosting::{Host, HostingError, Party};
+
+/// Waitlist priority of parties with a reservation, so they go ahead of walk-ins under
+/// `SeatingStrategy::Priority`.
+pub const RESERVATION_PRIORITY: u8 = 1;
+
+/// Minutes in a day, for converting between slots and absolute minutes.
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// A calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// Whether `year` has a February 29th in the Gregorian calendar.
+    pub fn is_leap_year(year: u16) -> bool {
+        year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+    }
+
+    /// The number of days in `month` of `year`, or 0 if `month` isn't between 1 and 12.
+    pub fn days_in_month(year: u16, month: u8) -> u8 {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if Date::is_leap_year(year) => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    /// Parses `YYYY-MM-DD`, accepting only days that exist in that month.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid date '{s}'");
+        let mut parts = s.split('-');
+        let (Some(year), Some(month), Some(day), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let date = Date {
+            year: year.parse().map_err(|_| invalid())?,
+            month: month.parse().map_err(|_| invalid())?,
+            day: day.parse().map_err(|_| invalid())?,
+        };
+        if !(1..=Date::days_in_month(date.year, date.month)).contains(&date.day) {
+            return Err(invalid());
+        }
+        Ok(date)
+    }
+}
+
+/// When a reservation starts: a date and a time of day in minutes after midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeSlot {
+    pub date: Date,
+    pub start: u16,
+}
+
+impl TimeSlot {
+    /// Creates a slot starting at `hour:minute` on `date`.
+    ///
+    /// # Panics
+    ///
+    /// If `hour` is 24 or more or `minute` 60 or more; `TimeSlot::try_at` returns `None` instead.
+    pub fn at(date: Date, hour: u16, minute: u16) -> TimeSlot {
+        TimeSlot::try_at(date, hour, minute)
+            .unwrap_or_else(|| panic!("{hour:02}:{minute:02} is not a time of day"))
+    }
+
+    /// Creates a slot starting at `hour:minute` on `date`, if that is a time of day.
+    pub fn try_at(date: Date, hour: u16, minute: u16) -> Option<TimeSlot> {
+        (hour < 24 && minute < 60).then_some(TimeSlot {
+            date,
+            start: hour * 60 + minute,
+        })
+    }
+
+    /// Minutes since 1970-01-01 00:00, so slots on different days can be compared and added to.
+    fn minutes(self) -> i64 {
+        days_from_civil(self.date) * MINUTES_PER_DAY + i64::from(self.start)
+    }
+}
+
+impl fmt::Display for TimeSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {:02}:{:02}", self.date, self.start / 60, self.start % 60)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReservationStatus {
+    Booked,
+    /// The party showed up and joined the waitlist.
+    Arrived,
+    Cancelled,
+    NoShow,
+}
+
+impl ReservationStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            ReservationStatus::Booked => "booked",
+            ReservationStatus::Arrived => "arrived",
+            ReservationStatus::Cancelled => "cancelled",
+            ReservationStatus::NoShow => "no-show",
+        }
+    }
+}
+
+impl FromStr for ReservationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "booked" => Ok(ReservationStatus::Booked),
+            "arrived" => Ok(ReservationStatus::Arrived),
+            "cancelled" => Ok(ReservationStatus::Cancelled),
+            "no-show" => Ok(ReservationStatus::NoShow),
+            other => Err(format!("unknown reservation status '{other}'")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reservation {
+    pub id: u32,
+    pub name: String,
+    pub size: u32,
+    pub slot: TimeSlot,
+    pub status: ReservationStatus,
+}
+
+impl Reservation {
+    /// Whether the reservation still holds seats.
+    pub fn is_active(&self) -> bool {
+        matches!(self.status, ReservationStatus::Booked | ReservationStatus::Arrived)
+    }
+}
+
+#[derive(Debug)]
+pub enum ReservationError {
+    Io(io::Error),
+    /// A line of the reservations file couldn't be understood. Lines are numbered from 1.
+    Parse { line: usize, message: String },
+    Hosting(HostingError),
+    /// Names can't be empty or contain tabs or line breaks, which the file format relies on.
+    InvalidName(String),
+    /// The slot doesn't have enough seats left for the party.
+    Full { slot: TimeSlot, available: u32 },
+    UnknownReservation(u32),
+    /// Only booked reservations can be cancelled, checked in or marked as no-shows.
+    NotBooked { id: u32, status: ReservationStatus },
+}
+
+impl fmt::Display for ReservationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReservationError::Io(error) => write!(f, "couldn't access the reservations: {error}"),
+            ReservationError::Parse { line, message } => {
+                write!(f, "reservations line {line}: {message}")
+            }
+            ReservationError::Hosting(error) => write!(f, "{error}"),
+            ReservationError::InvalidName(name) => write!(f, "invalid party name {name:?}"),
+            ReservationError::Full { slot, available } => {
+                write!(f, "only {available} seats left at {slot}")
+            }
+            ReservationError::UnknownReservation(id) => write!(f, "there is no reservation {id}"),
+            ReservationError::NotBooked { id, status } => {
+                write!(f, "reservation {id} is {}", status.as_str())
+            }
+        }
+    }
+}
+
+impl Error for ReservationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReservationError::Io(error) => Some(error),
+            ReservationError::Hosting(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReservationError {
+    fn from(error: io::Error) -> Self {
+        ReservationError::Io(error)
+    }
+}
+
+impl From<HostingError> for ReservationError {
+    fn from(error: HostingError) -> Self {
+        ReservationError::Hosting(error)
+    }
+}
+
+/// The reservations of the restaurant.
+///
+/// Every reservation holds its seats for `slot_length` minutes from its start, and no more than
+/// `capacity` seats can be held at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservationBook {
+    pub capacity: u32,
+    pub slot_length: u16,
+    reservations: Vec<Reservation>,
+}
+
+impl ReservationBook {
+    pub fn new(capacity: u32, slot_length: u16) -> ReservationBook {
+        ReservationBook {
+            capacity,
+            slot_length,
+            reservations: Vec::new(),
+        }
+    }
+
+    pub fn reservations(&self) -> &[Reservation] {
+        &self.reservations
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Reservation> {
+        self.reservations.iter().find(|reservation| reservation.id == id)
+    }
+
+    /// Seats still free for the whole of a reservation starting at `slot`.
+    ///
+    /// Late reservations run on past midnight and hold their seats on the next day too.
+    pub fn available(&self, slot: TimeSlot) -> u32 {
+        let length = i64::from(self.slot_length);
+        let (begin, end) = (slot.minutes(), slot.minutes() + length);
+        let active = || {
+            self.reservations
+                .iter()
+                .filter(|reservation| reservation.is_active())
+        };
+
+        let held_at = |moment: i64| -> u32 {
+            active()
+                .filter(|reservation| {
+                    let start = reservation.slot.minutes();
+                    start <= moment && moment < start + length
+                })
+                .map(|reservation| reservation.size)
+                .sum()
+        };
+
+        // Seats held only go up when a reservation starts, so the busiest moment of the slot is
+        // its own start or the start of a reservation beginning during it
+        let busiest = active()
+            .map(|reservation| reservation.slot.minutes())
+            .filter(|&start| begin < start && start < end)
+            .chain([begin])
+            .map(held_at)
+            .max()
+            .unwrap_or(0);
+
+        self.capacity.saturating_sub(busiest)
+    }
+
+    /// Books a table and returns the reservation's id.
+    pub fn book(
+        &mut self,
+        name: &str,
+        size: u32,
+        slot: TimeSlot,
+    ) -> Result<u32, ReservationError> {
+        if name.trim().is_empty() || name.contains(['\t', '\n', '\r']) {
+            return Err(ReservationError::InvalidName(String::from(name)));
+        }
+        if size == 0 {
+            return Err(HostingError::EmptyParty.into());
+        }
+
+        let available = self.available(slot);
+        if size > available {
+            return Err(ReservationError::Full { slot, available });
+        }
+
+        let last_id = self.reservations.iter().map(|reservation| reservation.id).max();
+        let id = last_id.unwrap_or(0) + 1;
+        self.reservations.push(Reservation {
+            id,
+            name: String::from(name),
+            size,
+            slot,
+            status: ReservationStatus::Booked,
+        });
+        Ok(id)
+    }
+
+    /// Cancels a booking, freeing its seats.
+    pub fn cancel(&mut self, id: u32) -> Result<(), ReservationError> {
+        self.booked_mut(id)?.status = ReservationStatus::Cancelled;
+        Ok(())
+    }
+
+    /// Records that the party never came, freeing its seats.
+    pub fn mark_no_show(&mut self, id: u32) -> Result<(), ReservationError> {
+        self.booked_mut(id)?.status = ReservationStatus::NoShow;
+        Ok(())
+    }
+
+    /// Marks every booking that is more than `grace` minutes late at `now` as a no-show,
+    /// returning their ids.
+    ///
+    /// Bookings from earlier days count too, so a late booking whose grace period runs past
+    /// midnight is still caught after it.
+    pub fn mark_late_no_shows(&mut self, now: TimeSlot, grace: u16) -> Vec<u32> {
+        self.reservations
+            .iter_mut()
+            .filter(|reservation| {
+                reservation.status == ReservationStatus::Booked
+                    && reservation.slot.minutes() + i64::from(grace) < now.minutes()
+            })
+            .map(|reservation| {
+                reservation.status = ReservationStatus::NoShow;
+                reservation.id
+            })
+            .collect()
+    }
+
+    /// Checks a party in, putting it on the host's waitlist together with the walk-ins.
+    ///
+    /// Reserved parties get `RESERVATION_PRIORITY`, so a host seating by priority takes them
+    /// before walk-ins that arrived earlier.
+    pub fn check_in(
+        &mut self,
+        id: u32,
+        host: &mut Host,
+        arrived_at: u32,
+    ) -> Result<(), ReservationError> {
+        let reservation = self.booked_mut(id)?;
+        let party = Party::new(&reservation.name, reservation.size, arrived_at)
+            .with_priority(RESERVATION_PRIORITY);
+
+        host.add_to_waitlist(party)?;
+        reservation.status = ReservationStatus::Arrived;
+        Ok(())
+    }
+
+    /// Writes the reservations to a file, one per line. See `ReservationBook::load`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReservationError> {
+        let mut text = String::from("# id\tname\tsize\tdate\ttime\tstatus\n");
+
+        for reservation in &self.reservations {
+            text.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{:02}:{:02}\t{}\n",
+                reservation.id,
+                reservation.name,
+                reservation.size,
+                reservation.slot.date,
+                reservation.slot.start / 60,
+                reservation.slot.start % 60,
+                reservation.status.as_str(),
+            ));
+        }
+
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Reads reservations saved with `ReservationBook::save`: tab-separated id, name, size,
+    /// `YYYY-MM-DD` date, `HH:MM` time and status. Lines starting with `#` are ignored.
+    pub fn load(
+        path: impl AsRef<Path>,
+        capacity: u32,
+        slot_length: u16,
+    ) -> Result<ReservationBook, ReservationError> {
+        let mut book = ReservationBook::new(capacity, slot_length);
+
+        for (index, line) in fs::read_to_string(path)?.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: String| ReservationError::Parse {
+                line: index + 1,
+                message,
+            };
+
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [id, name, size, date, time, status] = fields[..] else {
+                return Err(error(format!("expected 6 fields, found {}", fields.len())));
+            };
+
+            let date = date.parse().map_err(error)?;
+            let slot = time
+                .split_once(':')
+                .and_then(|(hour, minute)| Some((hour.parse().ok()?, minute.parse().ok()?)))
+                .and_then(|(hour, minute)| TimeSlot::try_at(date, hour, minute))
+                .ok_or_else(|| error(format!("invalid time '{time}'")))?;
+
+            book.reservations.push(Reservation {
+                id: id.parse().map_err(|_| error(format!("invalid id '{id}'")))?,
+                name: String::from(name),
+                size: size.parse().map_err(|_| error(format!("invalid size '{size}'")))?,
+                slot,
+                status: status.parse().map_err(error)?,
+            });
+        }
+
+        Ok(book)
+    }
+
+    /// Exports the reservations as an iCalendar (RFC 5545) document, one event per reservation.
+    ///
+    /// Times are written as floating local times, since the restaurant has a single time zone.
+    /// Each event is stamped with the time of the export, in UTC as the standard requires.
+    pub fn to_icalendar(&self) -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let stamp = ical_utc_date_time(now);
+
+        let mut lines = vec![
+            String::from("BEGIN:VCALENDAR"),
+            String::from("VERSION:2.0"),
+            String::from("PRODID:-//restaurant//reservations//EN"),
+        ];
+
+        for reservation in &self.reservations {
+            let start = ical_date_time(reservation.slot);
+            let status = match reservation.status {
+                ReservationStatus::Booked | ReservationStatus::Arrived => "CONFIRMED",
+                ReservationStatus::Cancelled | ReservationStatus::NoShow => "CANCELLED",
+            };
+
+            lines.extend([
+                String::from("BEGIN:VEVENT"),
+                format!("UID:reservation-{}@restaurant.localhost", reservation.id),
+                format!("DTSTAMP:{stamp}"),
+                format!("DTSTART:{start}"),
+                format!("DURATION:PT{}M", self.slot_length),
+                format!(
+                    "SUMMARY:{}",
+                    ical_escape(&format!("Table for {} - {}", reservation.size, reservation.name))
+                ),
+                format!("STATUS:{status}"),
+                String::from("END:VEVENT"),
+            ]);
+        }
+
+        lines.push(String::from("END:VCALENDAR"));
+        // iCalendar lines end with CRLF
+        lines.iter().map(|line| ical_fold(line) + "\r\n").collect()
+    }
+
+    fn booked_mut(&mut self, id: u32) -> Result<&mut Reservation, ReservationError> {
+        let reservation = self
+            .reservations
+            .iter_mut()
+            .find(|reservation| reservation.id == id)
+            .ok_or(ReservationError::UnknownReservation(id))?;
+
+        if reservation.status != ReservationStatus::Booked {
+            return Err(ReservationError::NotBooked {
+                id,
+                status: reservation.status,
+            });
+        }
+        Ok(reservation)
+    }
+}
+
+/// Formats a slot as an iCalendar local date-time.
+fn ical_date_time(slot: TimeSlot) -> String {
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}00",
+        slot.date.year,
+        slot.date.month,
+        slot.date.day,
+        slot.start / 60,
+        slot.start % 60
+    )
+}
+
+/// Formats seconds since the Unix epoch as an iCalendar UTC date-time.
+fn ical_utc_date_time(seconds: u64) -> String {
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    let time = seconds % 86_400;
+    format!(
+        "{year:04}{month:02}{day:02}T{:02}{:02}{:02}Z",
+        time / 3_600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+/// Splits a content line into lines of at most 75 octets, each continuation starting with a
+/// space, as RFC 5545 asks. Characters are never split.
+fn ical_fold(line: &str) -> String {
+    const MAX_OCTETS: usize = 75;
+    let mut folded = String::with_capacity(line.len());
+    let mut octets = 0;
+
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded
+}
+
+/// The number of days from 1970-01-01 to `date`, in the proleptic Gregorian calendar.
+fn days_from_civil(date: Date) -> i64 {
+    // Count years from March, so the leap day comes last; eras are 400 years long
+    let month = i64::from(date.month);
+    let year = i64::from(date.year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5
+        + i64::from(date.day)
+        - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date `days` after 1970-01-01, as year, month and day. The inverse of `days_from_civil`.
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u8;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u8;
+    (year_of_era + era * 400 + i64::from(month <= 2), month, day)
+}
+
+/// Escapes the characters iCalendar text values treat specially.
+fn ical_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,40 @@
+use crate::billing::{self, Bill, BillingError, Money, PaymentMethod, Receipt};
+use crate::order::{Order, OrderError, OrderStatus};
+
+/// Writes down what a table wants. Every order starts out as placed.
+pub fn take_order(id: u32, table: u32, items: Vec<String>) -> Order {
+    Order::new(id, table, items)
+}
+
+/// Brings a ready order to its table.
+pub fn serve_order(order: &mut Order) -> Result<(), OrderError> {
+    order.advance(OrderStatus::Served)
+}
+
+/// Settles the bill of a served order and marks it as paid.
+///
+/// Each payment method is charged the amount next to it; together they must cover the bill.
+/// Nobody is charged if the order hasn't been served yet, or if the bill is for another order.
+pub fn take_payment(
+    order: &mut Order,
+    bill: &Bill,
+    payments: &mut [(&mut dyn PaymentMethod, Money)],
+) -> Result<Receipt, BillingError> {
+    if bill.order != order.id() {
+        return Err(BillingError::WrongBill {
+            order: order.id(),
+            bill: bill.order,
+        });
+    }
+    if !order.status().can_transition_to(OrderStatus::Paid) {
+        return Err(BillingError::Order(OrderError {
+            order: order.id(),
+            from: order.status(),
+            to: OrderStatus::Paid,
+        }));
+    }
+
+    let receipt = billing::settle(bill, payments)?;
+    order.advance(OrderStatus::Paid)?;
+    Ok(receipt)
+}
@@ -0,0 +1,100 @@
+//! A restaurant, grown out of the chapter 7 module examples.
+//!
+//! The front of house (hosting guests and serving them) and the back of house (the kitchen) are
+//! separate modules, each in its own file. `front_of_house` itself stays private: its modules are
+//! re-exported with `pub use`, so callers write `restaurant::hosting` instead of
+//! `restaurant::front_of_house::hosting`, and the internal layout can change without breaking them.
+//! The types used across modules are re-exported at the root as well.
+//!
+//! `api` serves the order pipeline over HTTP; `cargo run` starts it on port 7878.
+//!
+//! # Paths
+//!
+//! An item can be named by an absolute path, starting from `crate` (as in
+//! `crate::front_of_house::hosting`), or by a relative path, starting from the current module
+//! (`front_of_house::hosting`), from its parent (`super::`) or from `self::`. Absolute paths keep
+//! working when the code that uses them moves elsewhere; relative paths keep working when the code
+//! moves together with what it refers to. This crate mostly uses absolute paths.
+//!
+//! `use` brings a path into scope for the module it's written in, and only that module. By
+//! convention functions are brought in through their parent module, so `serving::take_order`
+//! shows it isn't defined locally, while structs, enums and other items are brought in by their
+//! full path, like `Card` below. Two items with the same name can't both be brought in: either
+//! keep their parents (`fmt::Result` and `io::Result`) or rename one with `as`, as in
+//! `use std::io::Result as IoResult;`.
+//!
+//! `pub use` re-exports a name, so code outside the crate can use it as if it were defined here.
+//! That is how `restaurant::hosting` and the types below are reachable.
+
+mod front_of_house;
+pub mod api;
+pub mod back_of_house;
+pub mod billing;
+pub mod menu;
+pub mod order;
+pub mod shift;
+
+pub use crate::back_of_house::kitchen;
+pub use crate::front_of_house::{hosting, reservations, serving};
+
+pub use crate::back_of_house::inventory::{Inventory, InventoryError};
+pub use crate::billing::{Bill, BillingError, Money, PaymentMethod, Receipt, Tip};
+pub use crate::hosting::{Host, HostingError, Party, SeatingStrategy, Table};
+pub use crate::kitchen::{Kitchen, KitchenError, KitchenReport};
+pub use crate::menu::{Menu, MenuError, MenuItem, Season};
+pub use crate::order::{Order, OrderError, OrderStatus};
+pub use crate::reservations::{Date, ReservationBook, ReservationError, TimeSlot};
+pub use crate::shift::{Shift, ShiftError, Waiter};
+
+use std::error::Error;
+
+use crate::back_of_house::{Appetizer, Breakfast};
+use crate::billing::Card;
+
+/// Hands a cooked order over to the front of house, marking it as ready.
+///
+/// It lives at the root because both halves of the restaurant use it; the back of house reaches
+/// it through `super::deliver_order`.
+pub fn deliver_order(order: &mut Order) -> Result<(), OrderError> {
+    order.advance(OrderStatus::Ready)
+}
+
+/// Takes a table through a whole summer meal off the default menu: order, cook, serve and pay.
+///
+/// Returns the paid order, or the first step that failed.
+pub fn eat_at_restaurant() -> Result<Order, Box<dyn Error>> {
+    let mut host = Host::new(vec![Table::new(1, 2), Table::new(2, 4)], SeatingStrategy::BestFit);
+    host.add_to_waitlist(Party::new("Ferris", 2, 0))
+        .expect("a table of two fits a party of two");
+    let table = host.seat_at_table(0)[0].table;
+
+    // Order a breakfast in the summer with Rye toast, then change our mind about the bread
+    let mut breakfast = Breakfast::summer("Rye");
+    breakfast.toast = String::from("Wheat");
+    println!("I'd like {} toast please", breakfast.toast);
+
+    let menu = Menu::default();
+    let items = menu.order(&[Appetizer::Soup.menu_name(), breakfast.menu_name()], Season::Summer)?;
+    let names = items.iter().map(|item| item.name.clone()).collect();
+
+    let mut order = serving::take_order(1, table, names);
+
+    let mut kitchen = Kitchen::standard();
+    kitchen.cook_order(&mut order, 0)?;
+    let report = kitchen.run();
+    println!("The kitchen took {} seconds", report.elapsed);
+    deliver_order(&mut order)?;
+
+    serving::serve_order(&mut order)?;
+
+    // 8.25% sales tax and an 18% tip, all on one card
+    let bill = Bill::for_order(&order, &menu, 825)?.with_tip(Tip::Percent(1_800));
+    let mut card = Card {
+        last_four: String::from("4242"),
+        available: Money::from_cents(10_000),
+    };
+    let receipt = serving::take_payment(&mut order, &bill, &mut [(&mut card, bill.total())])?;
+    print!("{receipt}");
+
+    Ok(order)
+}
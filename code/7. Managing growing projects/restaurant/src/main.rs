@@ -0,0 +1,14 @@
+use restaurant::api::{Config, Server};
+
+fn main() {
+    let server = match Server::start("127.0.0.1:7878", Config::default()) {
+        Ok(server) => server,
+        Err(error) => {
+            eprintln!("Couldn't start the server: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    println!("Taking orders on http://{}", server.address());
+    server.wait();
+}
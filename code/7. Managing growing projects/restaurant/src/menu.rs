@@ -0,0 +1,299 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The menu the restaurant opens with, bundled into the crate.
+pub const DEFAULT_MENU: &str = include_str!("../data/menu.txt");
+
+/// Seasons of the year, which decide what's on offer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub const ALL: [Season; 4] = [Season::Spring, Season::Summer, Season::Autumn, Season::Winter];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Appetizer,
+    Breakfast,
+    Main,
+    Dessert,
+    Drink,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Allergen {
+    Celery,
+    Dairy,
+    Egg,
+    Fish,
+    Gluten,
+    Mustard,
+    Nuts,
+    Shellfish,
+    Soy,
+}
+
+/// Implements `FromStr` for a field-less enum by matching lowercase names.
+macro_rules! parse_by_name {
+    ($type:ty, $what:literal, { $($name:literal => $variant:expr),+ $(,)? }) => {
+        impl FromStr for $type {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.trim().to_lowercase().as_str() {
+                    $($name => Ok($variant),)+
+                    other => Err(format!("unknown {} '{}'", $what, other)),
+                }
+            }
+        }
+    };
+}
+
+parse_by_name!(Season, "season", {
+    "spring" => Season::Spring,
+    "summer" => Season::Summer,
+    "autumn" => Season::Autumn,
+    "fall" => Season::Autumn,
+    "winter" => Season::Winter,
+});
+
+parse_by_name!(Category, "category", {
+    "appetizer" => Category::Appetizer,
+    "breakfast" => Category::Breakfast,
+    "main" => Category::Main,
+    "dessert" => Category::Dessert,
+    "drink" => Category::Drink,
+});
+
+parse_by_name!(Allergen, "allergen", {
+    "celery" => Allergen::Celery,
+    "dairy" => Allergen::Dairy,
+    "egg" => Allergen::Egg,
+    "fish" => Allergen::Fish,
+    "gluten" => Allergen::Gluten,
+    "mustard" => Allergen::Mustard,
+    "nuts" => Allergen::Nuts,
+    "shellfish" => Allergen::Shellfish,
+    "soy" => Allergen::Soy,
+});
+
+/// A dish or drink that can be ordered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    pub name: String,
+    pub category: Category,
+    /// Price in cents, so amounts add up exactly.
+    pub price: u32,
+    pub seasons: Vec<Season>,
+    pub allergens: Vec<Allergen>,
+}
+
+impl MenuItem {
+    pub fn is_available_in(&self, season: Season) -> bool {
+        self.seasons.contains(&season)
+    }
+
+    pub fn contains(&self, allergen: Allergen) -> bool {
+        self.allergens.contains(&allergen)
+    }
+}
+
+#[derive(Debug)]
+pub enum MenuError {
+    Io(io::Error),
+    /// A line of the menu file couldn't be understood. Lines are numbered from 1.
+    Parse { line: usize, message: String },
+    UnknownItem(String),
+    OutOfSeason { item: String, season: Season },
+    SoldOut(String),
+}
+
+impl fmt::Display for MenuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MenuError::Io(error) => write!(f, "couldn't read the menu: {error}"),
+            MenuError::Parse { line, message } => write!(f, "menu line {line}: {message}"),
+            MenuError::UnknownItem(name) => write!(f, "'{name}' is not on the menu"),
+            MenuError::OutOfSeason { item, season } => {
+                write!(f, "'{item}' is not available in {season:?}")
+            }
+            MenuError::SoldOut(item) => write!(f, "'{item}' is sold out"),
+        }
+    }
+}
+
+impl Error for MenuError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MenuError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MenuError {
+    fn from(error: io::Error) -> Self {
+        MenuError::Io(error)
+    }
+}
+
+/// Everything the restaurant serves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Menu {
+    items: Vec<MenuItem>,
+    /// Items that are on the menu but can't be made right now.
+    sold_out: Vec<String>,
+}
+
+impl Menu {
+    /// Reads a menu file. See `Menu::parse` for the format.
+    pub fn load(path: impl AsRef<Path>) -> Result<Menu, MenuError> {
+        Menu::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parses a menu, one item per line:
+    ///
+    /// ```text
+    /// category | name | price in cents | seasons (or "all") | allergens
+    /// ```
+    ///
+    /// Seasons and allergens are comma-separated lists. Blank lines and lines starting with `#`
+    /// are ignored.
+    pub fn parse(text: &str) -> Result<Menu, MenuError> {
+        let mut items = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: String| MenuError::Parse {
+                line: index + 1,
+                message,
+            };
+
+            let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+            let [category, name, price, seasons, allergens] = fields[..] else {
+                return Err(error(format!("expected 5 fields, found {}", fields.len())));
+            };
+
+            let seasons = if seasons.eq_ignore_ascii_case("all") {
+                Season::ALL.to_vec()
+            } else {
+                parse_list(seasons).map_err(error)?
+            };
+
+            items.push(MenuItem {
+                name: String::from(name),
+                category: category.parse().map_err(error)?,
+                price: price
+                    .parse()
+                    .map_err(|_| error(format!("invalid price '{price}'")))?,
+                seasons,
+                allergens: parse_list(allergens).map_err(error)?,
+            });
+        }
+
+        Ok(Menu {
+            items,
+            sold_out: Vec::new(),
+        })
+    }
+
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    /// Looks an item up by name, ignoring case.
+    pub fn item(&self, name: &str) -> Option<&MenuItem> {
+        self.items
+            .iter()
+            .find(|item| item.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Items in season that aren't sold out.
+    pub fn available_in(&self, season: Season) -> impl Iterator<Item = &MenuItem> {
+        self.items
+            .iter()
+            .filter(move |item| item.is_available_in(season) && !self.is_sold_out(&item.name))
+    }
+
+    pub fn is_sold_out(&self, name: &str) -> bool {
+        self.sold_out.iter().any(|sold_out| sold_out.eq_ignore_ascii_case(name))
+    }
+
+    /// Marks an item as sold out, or as available again. Unknown names are ignored.
+    pub fn set_sold_out(&mut self, name: &str, sold_out: bool) {
+        let Some(item) = self.item(name) else {
+            return;
+        };
+        let name = item.name.clone();
+
+        self.sold_out.retain(|sold_out| *sold_out != name);
+        if sold_out {
+            self.sold_out.push(name);
+        }
+    }
+
+    pub fn in_category(&self, category: Category) -> impl Iterator<Item = &MenuItem> {
+        self.items
+            .iter()
+            .filter(move |item| item.category == category)
+    }
+
+    pub fn without(&self, allergen: Allergen) -> impl Iterator<Item = &MenuItem> {
+        self.items
+            .iter()
+            .filter(move |item| !item.contains(allergen))
+    }
+
+    /// Checks that every requested item exists, is in season and isn't sold out.
+    ///
+    /// Returns the matching menu items in the order they were asked for.
+    pub fn order(&self, names: &[&str], season: Season) -> Result<Vec<&MenuItem>, MenuError> {
+        names
+            .iter()
+            .map(|&name| {
+                let item = self
+                    .item(name)
+                    .ok_or_else(|| MenuError::UnknownItem(String::from(name)))?;
+
+                if !item.is_available_in(season) {
+                    return Err(MenuError::OutOfSeason {
+                        item: item.name.clone(),
+                        season,
+                    });
+                }
+                if self.is_sold_out(&item.name) {
+                    return Err(MenuError::SoldOut(item.name.clone()));
+                }
+                Ok(item)
+            })
+            .collect()
+    }
+}
+
+impl Default for Menu {
+    fn default() -> Self {
+        Menu::parse(DEFAULT_MENU).expect("the bundled menu is valid")
+    }
+}
+
+fn parse_list<T: FromStr<Err = String>>(list: &str) -> Result<Vec<T>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::parse)
+        .collect()
+}
@@ -0,0 +1,119 @@
+use std::error::Error;
+use std::fmt;
+
+/// The stages an order goes through, from the moment it's taken until it's paid for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+    Placed,
+    Cooking,
+    Ready,
+    Served,
+    Paid,
+}
+
+impl OrderStatus {
+    /// Returns whether an order may move from `self` to `next`.
+    ///
+    /// Besides the happy path, a ready or served order can go back to the kitchen when it has to
+    /// be remade. A paid order is final.
+    pub fn can_transition_to(self, next: OrderStatus) -> bool {
+        use OrderStatus::*;
+
+        matches!(
+            (self, next),
+            (Placed, Cooking)
+                | (Cooking, Ready)
+                | (Ready, Served)
+                | (Served, Paid)
+                | (Ready, Cooking)
+                | (Served, Cooking)
+        )
+    }
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            OrderStatus::Placed => "placed",
+            OrderStatus::Cooking => "cooking",
+            OrderStatus::Ready => "ready",
+            OrderStatus::Served => "served",
+            OrderStatus::Paid => "paid",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Error returned when an order is asked to skip or repeat a stage of its lifecycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderError {
+    pub order: u32,
+    pub from: OrderStatus,
+    pub to: OrderStatus,
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "order #{} can't go from {} to {}",
+            self.order, self.from, self.to
+        )
+    }
+}
+
+impl Error for OrderError {}
+
+/// An order taken at a table.
+///
+/// The status is private so it can only change through `advance`, which enforces the lifecycle
+/// placed → cooking → ready → served → paid.
+#[derive(Debug, Clone)]
+pub struct Order {
+    id: u32,
+    pub table: u32,
+    pub items: Vec<String>,
+    status: OrderStatus,
+    history: Vec<OrderStatus>,
+}
+
+impl Order {
+    /// Creates a freshly placed order.
+    pub fn new(id: u32, table: u32, items: Vec<String>) -> Order {
+        Order {
+            id,
+            table,
+            items,
+            status: OrderStatus::Placed,
+            history: vec![OrderStatus::Placed],
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn status(&self) -> OrderStatus {
+        self.status
+    }
+
+    /// Every status the order has been in, oldest first.
+    pub fn history(&self) -> &[OrderStatus] {
+        &self.history
+    }
+
+    /// Moves the order to `next`, or leaves it untouched if the transition isn't allowed.
+    pub fn advance(&mut self, next: OrderStatus) -> Result<(), OrderError> {
+        if !self.status.can_transition_to(next) {
+            return Err(OrderError {
+                order: self.id,
+                from: self.status,
+                to: next,
+            });
+        }
+
+        self.status = next;
+        self.history.push(next);
+        Ok(())
+    }
+}
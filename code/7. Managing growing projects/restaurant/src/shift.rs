@@ -0,0 +1,234 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+
+use crate::back_of_house;
+use crate::back_of_house::inventory::Inventory;
+use crate::back_of_house::kitchen::KitchenError;
+use crate::menu::Menu;
+use crate::order::Order;
+use crate::serving;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShiftError {
+    /// Every cook has gone home, so the ticket can't be cooked.
+    KitchenClosed,
+    /// A worker thread panicked; `worker` says which one.
+    WorkerPanicked { worker: String },
+}
+
+impl fmt::Display for ShiftError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShiftError::KitchenClosed => write!(f, "the kitchen is closed"),
+            ShiftError::WorkerPanicked { worker } => write!(f, "{worker} stopped working"),
+        }
+    }
+}
+
+impl Error for ShiftError {}
+
+/// What happened during a shift.
+#[derive(Debug)]
+pub struct ShiftReport {
+    /// Every order that was served, in the order they reached the tables.
+    pub served: Vec<Order>,
+    /// How many tickets each cook finished, indexed by cook.
+    pub tickets_per_cook: Vec<usize>,
+    /// Orders the kitchen couldn't cook, and why; usually an ingredient ran out.
+    pub turned_away: Vec<(Order, KitchenError)>,
+    /// What was left in the pantry at closing time.
+    pub inventory: Inventory,
+    /// The menu at closing time, with whatever ran out sold out.
+    pub menu: Menu,
+}
+
+/// The pantry and the menu the cooks share; cooking takes from one and updates the other.
+struct Stores {
+    inventory: Inventory,
+    menu: Menu,
+}
+
+/// What a cook did during the shift.
+struct CookReport {
+    cooked: usize,
+    turned_away: Vec<(Order, KitchenError)>,
+}
+
+/// Takes orders at the tables and hands them to the kitchen.
+///
+/// Waiters can be cloned and moved to other threads; each clone holds its own end of the ticket
+/// channel.
+#[derive(Debug, Clone)]
+pub struct Waiter {
+    tickets: Sender<Order>,
+    next_id: Arc<AtomicU32>,
+}
+
+impl Waiter {
+    /// Writes down an order and sends its ticket to the kitchen, returning the order's id.
+    pub fn take_order(&self, table: u32, items: Vec<String>) -> Result<u32, ShiftError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let order = serving::take_order(id, table, items);
+
+        self.tickets
+            .send(order)
+            .map_err(|_| ShiftError::KitchenClosed)?;
+        Ok(id)
+    }
+}
+
+/// A service with the front and back of house running as separate threads.
+///
+/// Waiters send tickets to the cooks over one channel; the cooks share its receiving end, so
+/// whichever cook is free picks up the next ticket. Cooked orders go over a second channel to a
+/// runner, who serves them. Closing the shift drops the channels in turn, so every thread
+/// finishes its work and stops on its own.
+pub struct Shift {
+    waiter: Waiter,
+    stores: Arc<Mutex<Stores>>,
+    cooks: Vec<JoinHandle<CookReport>>,
+    runner: JoinHandle<Vec<Order>>,
+}
+
+impl Shift {
+    /// Opens the restaurant with `cooks` cooks in the kitchen, cooking from `inventory`.
+    ///
+    /// Dishes are sold out on `menu` as soon as the pantry can't make another one.
+    pub fn open(cooks: usize, inventory: Inventory, menu: Menu) -> Shift {
+        let (ticket_sender, ticket_receiver) = mpsc::channel();
+        let (ready_sender, ready_receiver) = mpsc::channel();
+        let ticket_receiver = Arc::new(Mutex::new(ticket_receiver));
+        let stores = Arc::new(Mutex::new(Stores { inventory, menu }));
+
+        let cooks = (0..cooks)
+            .map(|_| {
+                let tickets = Arc::clone(&ticket_receiver);
+                let stores = Arc::clone(&stores);
+                let ready = Sender::clone(&ready_sender);
+                thread::spawn(move || cook(&tickets, &stores, &ready))
+            })
+            .collect();
+
+        // Only the cooks hold senders now, so the runner stops once they have all gone home
+        drop(ready_sender);
+        let runner = thread::spawn(move || run_orders(ready_receiver));
+
+        Shift {
+            waiter: Waiter {
+                tickets: ticket_sender,
+                next_id: Arc::new(AtomicU32::new(1)),
+            },
+            stores,
+            cooks,
+            runner,
+        }
+    }
+
+    /// Puts another waiter on the floor.
+    pub fn waiter(&self) -> Waiter {
+        self.waiter.clone()
+    }
+
+    /// Ends the shift once every ticket has been cooked and served.
+    ///
+    /// The kitchen keeps going until every `Waiter` has been dropped, so drop or join whatever
+    /// holds them first.
+    pub fn close(self) -> Result<ShiftReport, ShiftError> {
+        drop(self.waiter);
+
+        // Wait for every worker before giving up on any of them, so no thread outlives the shift
+        let cooks: Vec<Result<CookReport, ShiftError>> = self
+            .cooks
+            .into_iter()
+            .enumerate()
+            .map(|(index, cook)| {
+                cook.join().map_err(|_| ShiftError::WorkerPanicked {
+                    worker: format!("cook {index}"),
+                })
+            })
+            .collect();
+        let served = self.runner.join().map_err(|_| ShiftError::WorkerPanicked {
+            worker: String::from("the runner"),
+        });
+
+        let cooks = cooks.into_iter().collect::<Result<Vec<CookReport>, ShiftError>>()?;
+        let served = served?;
+
+        // Every cook has gone home, so nobody else holds the stores
+        let Stores { inventory, menu } = Arc::into_inner(self.stores)
+            .expect("the cooks have been joined")
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let tickets_per_cook = cooks.iter().map(|cook| cook.cooked).collect();
+        let turned_away = cooks.into_iter().flat_map(|cook| cook.turned_away).collect();
+        Ok(ShiftReport {
+            served,
+            tickets_per_cook,
+            turned_away,
+            inventory,
+            menu,
+        })
+    }
+}
+
+/// A cook's loop: take the next ticket, cook it, pass it on. Returns what the cook did once the
+/// ticket channel is closed and empty.
+fn cook(
+    tickets: &Mutex<Receiver<Order>>,
+    stores: &Mutex<Stores>,
+    ready: &Sender<Order>,
+) -> CookReport {
+    let mut report = CookReport {
+        cooked: 0,
+        turned_away: Vec::new(),
+    };
+
+    loop {
+        // The lock is released at the end of the statement, before cooking starts
+        let ticket = match tickets.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(_) => break,
+        };
+        let Ok(mut order) = ticket else {
+            break;
+        };
+
+        // The stores are only locked while the ingredients are taken out
+        let started = match stores.lock() {
+            Ok(mut stores) => {
+                let Stores { inventory, menu } = &mut *stores;
+                back_of_house::cook_order(&mut order, inventory, menu)
+            }
+            Err(_) => break,
+        };
+        if let Err(error) = started {
+            report.turned_away.push((order, error));
+            continue;
+        }
+
+        crate::deliver_order(&mut order).expect("a cooking order can be delivered");
+        report.cooked += 1;
+
+        if ready.send(order).is_err() {
+            break;
+        }
+    }
+
+    report
+}
+
+/// The runner's loop: bring each ready order to its table.
+fn run_orders(ready: Receiver<Order>) -> Vec<Order> {
+    ready
+        .into_iter()
+        .map(|mut order| {
+            serving::serve_order(&mut order).expect("orders arrive ready");
+            order
+        })
+        .collect()
+}
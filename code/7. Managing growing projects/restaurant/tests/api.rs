@@ -0,0 +1,166 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use restaurant::api::json::Json;
+use restaurant::api::{Config, Server};
+use restaurant::{Inventory, Season};
+
+/// Sends one request and returns the status code and the parsed body.
+fn request(address: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Json) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, Json::parse(body).unwrap())
+}
+
+fn wait_until_served(address: SocketAddr, id: u64) -> Json {
+    for _ in 0..100 {
+        let (status, order) = request(address, "GET", &format!("/orders/{id}"), "");
+        assert_eq!(status, 200);
+        if order.get("status").and_then(Json::as_str) == Some("served") {
+            return order;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("order #{id} was never served");
+}
+
+#[test]
+fn order_is_placed_cooked_served_and_paid() {
+    let server = Server::start("127.0.0.1:0", Config::default()).unwrap();
+    let address = server.address();
+
+    let (status, menu) = request(address, "GET", "/menu", "");
+    assert_eq!(status, 200);
+    let names: Vec<&str> = menu
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|item| item.get("name").and_then(Json::as_str))
+        .collect();
+    assert!(names.contains(&"Soup"));
+
+    let (status, order) = request(
+        address,
+        "POST",
+        "/orders",
+        r#"{"table": 2, "items": ["Soup", "Grilled Fish", "Coffee"]}"#,
+    );
+    assert_eq!(status, 201);
+    assert_eq!(order.get("status").and_then(Json::as_str), Some("cooking"));
+    let id = order.get("id").and_then(Json::as_u64).unwrap();
+
+    let order = wait_until_served(address, id);
+    assert_eq!(order.get("table").and_then(Json::as_u64), Some(2));
+    assert_eq!(
+        order.get("history").unwrap().to_string(),
+        r#"["placed","cooking","ready","served"]"#
+    );
+
+    // 30.50 + 2.52 tax + 3.00 tip, paid with a fifty
+    let (status, paid) = request(
+        address,
+        "POST",
+        &format!("/orders/{id}/payment"),
+        r#"{"method": "cash", "tendered_cents": 5000, "tip_cents": 300}"#,
+    );
+    assert_eq!(status, 200, "{paid}");
+    assert_eq!(paid.get("total_cents").and_then(Json::as_u64), Some(3_602));
+    assert_eq!(paid.get("change_cents").and_then(Json::as_u64), Some(1_398));
+    assert!(paid.get("receipt").and_then(Json::as_str).unwrap().contains("Grilled Fish"));
+
+    let (_, order) = request(address, "GET", &format!("/orders/{id}"), "");
+    assert_eq!(order.get("status").and_then(Json::as_str), Some("paid"));
+
+    server.shutdown();
+}
+
+#[test]
+fn bad_requests_get_errors() {
+    let server = Server::start("127.0.0.1:0", Config::default()).unwrap();
+    let address = server.address();
+
+    let error = |(status, body): (u16, Json)| {
+        assert!(body.get("error").is_some(), "{body}");
+        status
+    };
+
+    assert_eq!(error(request(address, "GET", "/orders/42", "")), 404);
+    assert_eq!(error(request(address, "GET", "/kitchen", "")), 404);
+    assert_eq!(error(request(address, "DELETE", "/menu", "")), 405);
+    assert_eq!(error(request(address, "POST", "/orders", "{not json")), 400);
+    assert_eq!(error(request(address, "POST", "/orders", &"[".repeat(10_000))), 400);
+    assert_eq!(error(request(address, "POST", "/orders", r#"{"table": 1, "items": []}"#)), 400);
+    assert_eq!(
+        error(request(address, "POST", "/orders", r#"{"table": 1, "items": ["Lobster"]}"#)),
+        400
+    );
+
+    let (_, order) = request(address, "POST", "/orders", r#"{"table": 1, "items": ["Soup"]}"#);
+    let id = order.get("id").and_then(Json::as_u64).unwrap();
+    wait_until_served(address, id);
+    let payment = format!("/orders/{id}/payment");
+
+    assert_eq!(
+        error(request(address, "POST", &payment, r#"{"method": "cheque", "tendered_cents": 1}"#)),
+        400
+    );
+    assert_eq!(
+        error(request(address, "POST", &payment, r#"{"method": "card", "tendered_cents": 100}"#)),
+        402
+    );
+    let (status, _) =
+        request(address, "POST", &payment, r#"{"method": "card", "tendered_cents": 10000}"#);
+    assert_eq!(status, 200);
+    assert_eq!(
+        error(request(address, "POST", &payment, r#"{"method": "card", "tendered_cents": 10000}"#)),
+        409,
+        "an order is only paid once"
+    );
+
+    server.shutdown();
+}
+
+#[test]
+fn orders_the_pantry_cannot_cover_are_turned_away() {
+    let mut inventory = Inventory::new(Season::Summer);
+    inventory.restock_for("Coffee", 1).unwrap();
+    let config = Config {
+        inventory,
+        ..Config::default()
+    };
+    let server = Server::start("127.0.0.1:0", config).unwrap();
+    let address = server.address();
+
+    let sold_out = |name: &str| {
+        let (_, menu) = request(address, "GET", "/menu", "");
+        !menu.to_string().contains(name)
+    };
+    let coffee = r#"{"table": 1, "items": ["Coffee"]}"#;
+
+    // Nobody has tried the lemonade yet, so only the kitchen finds out there are no lemons
+    assert!(!sold_out("Lemonade"));
+    let (status, body) =
+        request(address, "POST", "/orders", r#"{"table": 1, "items": ["Lemonade"]}"#);
+    assert_eq!(status, 409, "{body}");
+    assert!(sold_out("Lemonade"));
+
+    let (status, _) = request(address, "POST", "/orders", coffee);
+    assert_eq!(status, 201);
+    assert!(sold_out("Coffee"));
+    let (status, body) = request(address, "POST", "/orders", coffee);
+    assert_eq!(status, 400, "{body}");
+
+    server.shutdown();
+}
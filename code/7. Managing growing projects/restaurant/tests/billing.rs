@@ -0,0 +1,119 @@
+use restaurant::billing::{
+    self, Bill, BillingError, Card, Cash, GiftCard, Money, PaymentMethod, Tip,
+};
+use restaurant::menu::Menu;
+use restaurant::serving;
+
+fn dinner_bill() -> Bill {
+    // Soup 6.50 + Grilled Fish 21.00 + Coffee 3.00 = 30.50
+    let order = serving::take_order(
+        9,
+        2,
+        vec![
+            String::from("Soup"),
+            String::from("Grilled Fish"),
+            String::from("Coffee"),
+        ],
+    );
+    Bill::for_order(&order, &Menu::default(), 825).unwrap()
+}
+
+#[test]
+fn bill_adds_tax_and_tip_to_the_cent() {
+    let bill = dinner_bill().with_tip(Tip::Percent(1_500));
+
+    assert_eq!(bill.subtotal(), Money::from_cents(3_050));
+    // 8.25% of 30.50 is 2.51625, rounded to 2.52
+    assert_eq!(bill.tax(), Money::from_cents(252));
+    assert_eq!(bill.tip(), Money::from_cents(458));
+    assert_eq!(bill.total(), Money::from_cents(3_760));
+    assert_eq!(bill.total().to_string(), "$37.60");
+}
+
+#[test]
+fn unknown_items_cannot_be_billed() {
+    let order = serving::take_order(1, 1, vec![String::from("Pizza")]);
+    assert!(matches!(
+        Bill::for_order(&order, &Menu::default(), 0),
+        Err(BillingError::Menu(_))
+    ));
+}
+
+#[test]
+fn splits_always_add_up_to_the_total() {
+    let bill = dinner_bill().with_tip(Tip::Amount(Money::from_cents(1)));
+    let total = bill.total();
+
+    let even = bill.split_evenly(3);
+    assert_eq!(even.iter().copied().sum::<Money>(), total);
+    assert!(even.iter().max().unwrap().cents() - even.iter().min().unwrap().cents() <= 1);
+
+    let by_items = bill.split_by_items(&[vec![0, 2], vec![1]]).unwrap();
+    assert_eq!(by_items.iter().copied().sum::<Money>(), total);
+    assert!(by_items[1] > by_items[0]);
+
+    // Every line has to be claimed exactly once
+    assert_eq!(bill.split_by_items(&[vec![0], vec![1]]), None);
+    assert_eq!(bill.split_by_items(&[vec![0, 1, 2], vec![2]]), None);
+    assert_eq!(bill.split_by_items(&[vec![0, 1, 2, 3]]), None);
+}
+
+#[test]
+fn payment_methods_share_the_bill() {
+    let bill = dinner_bill();
+    let mut cash = Cash::new(Money::from_cents(2_000));
+    let mut card = Card {
+        last_four: String::from("1234"),
+        available: Money::from_cents(50_000),
+    };
+    let shares = bill.split_evenly(2);
+
+    let receipt =
+        billing::settle(&bill, &mut [(&mut cash, shares[0]), (&mut card, shares[1])]).unwrap();
+
+    assert_eq!(receipt.payments[0].change, Money::from_cents(2_000 - shares[0].cents()));
+    assert_eq!(card.available, Money::from_cents(50_000 - shares[1].cents()));
+
+    let printed = receipt.to_string();
+    assert!(printed.contains("Grilled Fish"));
+    assert!(printed.contains("Card ****1234"));
+    assert!(printed.lines().any(|line| line.starts_with("Total") && line.ends_with("$33.02")));
+}
+
+#[test]
+fn declined_payments_are_refunded() {
+    let bill = dinner_bill();
+    let mut card = Card {
+        last_four: String::from("1234"),
+        available: Money::from_cents(50_000),
+    };
+    let mut gift_card = GiftCard {
+        code: String::from("GIFT-1"),
+        balance: Money::from_cents(500),
+    };
+    let shares = bill.split_evenly(2);
+
+    let error = billing::settle(&bill, &mut [(&mut card, shares[0]), (&mut gift_card, shares[1])]);
+    assert!(matches!(
+        error,
+        Err(BillingError::Declined { method, .. }) if method == "Gift card GIFT-1"
+    ));
+    assert_eq!(card.available, Money::from_cents(50_000));
+
+    // Cash comes back exactly as tendered, change included
+    let mut cash = Cash::new(Money::from_cents(2_000));
+    let mut gift_card = GiftCard {
+        code: String::from("GIFT-2"),
+        balance: Money::ZERO,
+    };
+    let error = billing::settle(&bill, &mut [(&mut cash, shares[0]), (&mut gift_card, shares[1])]);
+    assert!(matches!(error, Err(BillingError::Declined { .. })));
+    assert_eq!(cash.tendered, Money::from_cents(2_000));
+    let receipt =
+        billing::settle(&bill, &mut [(&mut cash, shares[0]), (&mut card, shares[1])]).unwrap();
+    assert_eq!(receipt.payments[0].change, Money::from_cents(2_000 - shares[0].cents()));
+
+    let card: &mut dyn PaymentMethod = &mut card;
+    let error = billing::settle(&bill, &mut [(card, Money::from_cents(1))]);
+    assert!(matches!(error, Err(BillingError::AmountMismatch { .. })));
+}
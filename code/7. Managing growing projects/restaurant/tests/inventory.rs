@@ -0,0 +1,169 @@
+use restaurant::back_of_house::{self, Appetizer, Breakfast};
+use restaurant::{
+    serving, Inventory, InventoryError, Kitchen, KitchenError, Menu, MenuError, Order, Season,
+};
+
+fn order(id: u32, items: &[&str]) -> Order {
+    serving::take_order(id, 1, items.iter().map(|item| String::from(*item)).collect())
+}
+
+fn stocked_for_soup(portions: u32) -> Inventory {
+    let mut inventory = Inventory::new(Season::Autumn);
+    for (ingredient, amount) in Appetizer::Soup.ingredients() {
+        inventory.restock(&ingredient, amount * portions);
+    }
+    inventory
+}
+
+#[test]
+fn dishes_declare_their_ingredients() {
+    let inventory = Inventory::new(Season::Winter);
+    let breakfast = inventory.ingredients("Breakfast").unwrap();
+
+    assert!(breakfast.contains(&(String::from("oranges"), 1)));
+    assert_eq!(
+        Breakfast::for_season("Rye", Season::Spring).ingredients()[2],
+        (String::from("strawberries"), 1)
+    );
+    assert_eq!(inventory.ingredients("Soup"), Some(&Appetizer::Soup.ingredients()));
+}
+
+#[test]
+fn cooking_takes_ingredients_out_of_stock() {
+    let mut kitchen = Kitchen::standard().with_inventory(stocked_for_soup(2));
+
+    kitchen.cook_order(&mut order(1, &["Soup"]), 0).unwrap();
+
+    let inventory = kitchen.inventory().unwrap();
+    assert_eq!(inventory.stock("stock"), 300);
+    assert_eq!(inventory.stock("onions"), 1);
+    let used = inventory.ledger().last().unwrap();
+    assert!(used.change < 0);
+    assert_eq!(used.reason, "order #1");
+}
+
+#[test]
+fn orders_take_everything_they_need_or_nothing() {
+    let mut kitchen = Kitchen::standard().with_inventory(stocked_for_soup(1));
+    let mut greedy = order(1, &["Soup", "Soup"]);
+
+    let error = kitchen.cook_order(&mut greedy, 0).unwrap_err();
+    assert!(matches!(
+        error,
+        KitchenError::Inventory(InventoryError::OutOfStock { needed: 100, available: 50, .. })
+    ));
+    assert_eq!(kitchen.inventory().unwrap().stock("stock"), 300);
+    assert_eq!(greedy.status(), restaurant::OrderStatus::Placed);
+    assert!(kitchen.run().tickets.is_empty());
+}
+
+#[test]
+fn low_stock_is_reported() {
+    let mut inventory = stocked_for_soup(3);
+    inventory.set_reorder_level("celery", 50);
+    let mut kitchen = Kitchen::standard().with_inventory(inventory);
+
+    assert!(kitchen.cook_order(&mut order(1, &["Soup"]), 0).unwrap().is_empty());
+    assert_eq!(
+        kitchen.cook_order(&mut order(2, &["Soup"]), 0).unwrap(),
+        [(String::from("celery"), 50)]
+    );
+}
+
+#[test]
+fn breakfast_is_sold_out_without_seasonal_fruit() {
+    let mut inventory = Inventory::new(Season::Summer);
+    inventory.restock("bread", 10);
+    inventory.restock("butter", 100);
+    inventory.restock("peaches", 1);
+    let mut menu = Menu::default();
+
+    inventory.update_menu(&mut menu);
+    assert!(menu.order(&["Breakfast"], Season::Summer).is_ok());
+
+    inventory.use_for(&["Breakfast"], "order #1").unwrap();
+    inventory.update_menu(&mut menu);
+
+    assert!(matches!(
+        menu.order(&["Breakfast"], Season::Summer),
+        Err(MenuError::SoldOut(item)) if item == "Breakfast"
+    ));
+    assert!(menu.available_in(Season::Summer).all(|item| item.name != "Breakfast"));
+
+    // A delivery of peaches puts it back on the menu
+    inventory.restock("peaches", 6);
+    inventory.update_menu(&mut menu);
+    assert!(!menu.is_sold_out("Breakfast"));
+}
+
+#[test]
+fn cooking_sells_out_what_the_kitchen_can_no_longer_make() {
+    let mut inventory = stocked_for_soup(1);
+    let mut menu = Menu::default();
+    inventory.update_menu(&mut menu);
+    assert!(!menu.is_sold_out("Soup"));
+
+    back_of_house::cook_order(&mut order(1, &["Soup"]), &mut inventory, &mut menu).unwrap();
+    assert!(menu.is_sold_out("Soup"));
+
+    let mut second = order(2, &["Soup"]);
+    assert!(back_of_house::cook_order(&mut second, &mut inventory, &mut menu).is_err());
+    assert_eq!(second.status(), restaurant::OrderStatus::Placed);
+}
+
+#[test]
+fn a_kitchen_with_a_menu_sells_out_as_it_cooks() {
+    let mut kitchen = Kitchen::standard()
+        .with_inventory(stocked_for_soup(1))
+        .with_menu(Menu::default());
+
+    kitchen.cook_order(&mut order(1, &["Soup"]), 0).unwrap();
+
+    assert!(kitchen.menu().is_sold_out("Soup"));
+    assert!(matches!(
+        kitchen.menu().order(&["Soup"], Season::Autumn),
+        Err(MenuError::SoldOut(_))
+    ));
+}
+
+#[test]
+fn the_kitchen_and_the_free_functions_take_the_same_stock() {
+    let soup = || vec![String::from("Soup")];
+
+    let mut kitchen = Kitchen::standard().with_inventory(stocked_for_soup(2));
+    let mut simulated = order(1, &["Soup"]);
+    kitchen.cook_order(&mut simulated, 0).unwrap();
+    restaurant::deliver_order(&mut simulated).unwrap();
+    kitchen.fix_incorrect_order(&mut simulated, soup(), 10).unwrap();
+
+    let mut inventory = stocked_for_soup(2);
+    let mut menu = Menu::default();
+    let mut direct = order(1, &["Soup"]);
+    back_of_house::cook_order(&mut direct, &mut inventory, &mut menu).unwrap();
+    restaurant::deliver_order(&mut direct).unwrap();
+    back_of_house::fix_incorrect_order(&mut direct, soup(), &mut inventory, &mut menu).unwrap();
+
+    assert_eq!(simulated.history(), direct.history());
+    assert_eq!(kitchen.inventory().unwrap().ledger(), inventory.ledger());
+    assert_eq!(inventory.ledger().last().unwrap().reason, "remake of order #1");
+    assert_eq!(kitchen.menu(), &menu);
+    assert!(menu.is_sold_out("Soup"));
+}
+
+#[test]
+fn items_without_a_recipe_are_left_alone() {
+    let mut menu = Menu::parse(
+        "appetizer | Soup | 650 | all | celery\n\
+         appetizer | Bread Basket | 300 | all | gluten\n\
+         drink | Tap Water | 0 | all |",
+    )
+    .unwrap();
+    menu.set_sold_out("Tap Water", true);
+    let mut inventory = stocked_for_soup(2);
+
+    back_of_house::cook_order(&mut order(1, &["Soup"]), &mut inventory, &mut menu).unwrap();
+
+    assert!(menu.order(&["Bread Basket"], Season::Autumn).is_ok());
+    assert!(menu.is_sold_out("Tap Water"), "the inventory doesn't put it back on either");
+    assert!(!menu.is_sold_out("Soup"));
+}
@@ -0,0 +1,152 @@
+use restaurant::back_of_house::kitchen::{
+    Kitchen, KitchenError, Station,
+};
+use restaurant::order::OrderStatus;
+use restaurant::{deliver_order, serving};
+
+fn order(id: u32, items: &[&str]) -> restaurant::order::Order {
+    serving::take_order(id, 1, items.iter().map(|item| String::from(*item)).collect())
+}
+
+#[test]
+fn stations_cook_in_parallel_and_cooks_one_dish_at_a_time() {
+    let mut kitchen = Kitchen::standard();
+    // Soup (240s) and Breakfast (480s) share the stove, Coffee (90s) is at the bar
+    let mut first = order(1, &["Soup", "Coffee"]);
+    let mut second = order(2, &["Breakfast"]);
+    kitchen.cook_order(&mut first, 0).unwrap();
+    kitchen.cook_order(&mut second, 0).unwrap();
+
+    assert_eq!(first.status(), OrderStatus::Cooking);
+
+    let report = kitchen.run();
+    let finished: Vec<(u32, u32)> = report
+        .tickets
+        .iter()
+        .map(|ticket| (ticket.order, ticket.finished_at))
+        .collect();
+
+    assert_eq!(finished, [(1, 240), (2, 720)]);
+    assert_eq!(report.elapsed, 720);
+    assert_eq!(report.average_ticket_time, 480.0);
+    assert_eq!(report.throughput, 10.0);
+    assert_eq!(report.utilization["stove"], 1.0);
+}
+
+#[test]
+fn more_cooks_shorten_ticket_times() {
+    let rush = |cooks: u32| {
+        let mut kitchen = Kitchen::standard();
+        kitchen.set_cooks("grill", cooks).unwrap();
+        for id in 0..6 {
+            kitchen
+                .cook_order(&mut order(id, &["Grilled Fish", "Lemonade"]), id * 60)
+                .unwrap();
+        }
+        kitchen.run()
+    };
+
+    let one = rush(1);
+    let three = rush(3);
+
+    assert_eq!(one, rush(1), "the simulation is deterministic");
+    assert!(three.average_ticket_time < one.average_ticket_time);
+    assert!(three.throughput > one.throughput);
+    assert_eq!(three.tickets.len(), 6);
+}
+
+#[test]
+fn remakes_jump_the_queue() {
+    let mut kitchen = Kitchen::standard();
+    let mut wrong = order(1, &["Salad"]);
+    kitchen.cook_order(&mut wrong, 0).unwrap();
+    deliver_order(&mut wrong).unwrap();
+
+    // Three salads are queued at the cold station before the remake comes back
+    for id in 2..5 {
+        kitchen.cook_order(&mut order(id, &["Salad"]), 10).unwrap();
+    }
+    kitchen
+        .fix_incorrect_order(&mut wrong, vec![String::from("Chocolate Tart")], 20)
+        .unwrap();
+
+    let report = kitchen.run();
+    let order_of_completion: Vec<u32> = report.tickets.iter().map(|ticket| ticket.order).collect();
+
+    assert_eq!(order_of_completion, [1, 1, 2, 3, 4]);
+    assert_eq!(report.remakes, 1);
+    assert_eq!(wrong.status(), OrderStatus::Cooking);
+}
+
+#[test]
+fn unknown_dishes_and_stations_are_rejected() {
+    let mut kitchen = Kitchen::new(vec![Station::new("stove", 1), Station::new("grill", 0)]);
+    kitchen.add_recipe("Soup", "stove", 60);
+    kitchen.add_recipe("Steak", "grill", 600);
+
+    let mut pizza = order(1, &["Pizza"]);
+    assert!(matches!(
+        kitchen.cook_order(&mut pizza, 0),
+        Err(KitchenError::UnknownDish(dish)) if dish == "Pizza"
+    ));
+    assert_eq!(pizza.status(), OrderStatus::Placed);
+
+    assert!(matches!(
+        kitchen.cook_order(&mut order(2, &["Steak"]), 0),
+        Err(KitchenError::UnstaffedStation(_))
+    ));
+    assert!(matches!(
+        kitchen.set_cooks("bar", 1),
+        Err(KitchenError::UnknownStation(_))
+    ));
+    assert!(matches!(
+        kitchen.fix_incorrect_order(&mut order(3, &["Soup"]), Vec::new(), 0),
+        Err(KitchenError::Order(_))
+    ));
+}
+
+#[test]
+fn empty_tickets_are_done_when_they_arrive() {
+    let mut kitchen = Kitchen::standard();
+    kitchen.cook_order(&mut order(1, &[]), 30).unwrap();
+    kitchen.cook_order(&mut order(2, &["Coffee"]), 30).unwrap();
+
+    let report = kitchen.run();
+    let finished: Vec<(u32, u32)> = report
+        .tickets
+        .iter()
+        .map(|ticket| (ticket.order, ticket.finished_at))
+        .collect();
+
+    assert_eq!(finished, [(1, 30), (2, 120)]);
+}
+
+#[test]
+fn stations_with_work_keep_their_cooks() {
+    let mut kitchen = Kitchen::standard();
+    kitchen.set_cooks("bar", 0).unwrap();
+    kitchen.set_cooks("bar", 1).unwrap();
+    kitchen.cook_order(&mut order(1, &["Coffee"]), 0).unwrap();
+
+    assert!(matches!(
+        kitchen.set_cooks("bar", 0),
+        Err(KitchenError::StationBusy(station)) if station == "bar"
+    ));
+    kitchen.set_cooks("bar", 2).unwrap();
+    kitchen.set_cooks("stove", 0).unwrap();
+    assert_eq!(kitchen.run().tickets.len(), 1);
+}
+
+#[test]
+fn cooked_tickets_still_keep_their_stations_staffed() {
+    let mut kitchen = Kitchen::standard();
+    kitchen.cook_order(&mut order(1, &["Coffee"]), 0).unwrap();
+    let first = kitchen.run();
+
+    assert!(matches!(
+        kitchen.set_cooks("bar", 0),
+        Err(KitchenError::StationBusy(station)) if station == "bar"
+    ));
+    kitchen.set_cooks("stove", 0).unwrap();
+    assert_eq!(kitchen.run().tickets, first.tickets);
+}
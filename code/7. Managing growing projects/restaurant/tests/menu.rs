@@ -0,0 +1,59 @@
+use restaurant::back_of_house::{Appetizer, Breakfast};
+use restaurant::menu::{
+    Allergen, Category, Menu, MenuError, Season,
+};
+
+fn menu() -> Menu {
+    Menu::load(concat!(env!("CARGO_MANIFEST_DIR"), "/data/menu.txt")).unwrap()
+}
+
+#[test]
+fn menu_file_matches_the_bundled_menu() {
+    assert_eq!(menu(), Menu::default());
+}
+
+#[test]
+fn items_carry_category_price_and_allergens() {
+    let menu = menu();
+    let breakfast = menu.item("breakfast").unwrap();
+
+    assert_eq!(breakfast.category, Category::Breakfast);
+    assert_eq!(breakfast.price, 1150);
+    assert!(breakfast.contains(Allergen::Gluten));
+    assert_eq!(menu.in_category(Category::Appetizer).count(), 2);
+    assert!(menu.without(Allergen::Gluten).all(|item| !item.contains(Allergen::Gluten)));
+}
+
+#[test]
+fn availability_depends_on_the_season() {
+    let menu = menu();
+
+    assert!(menu.available_in(Season::Summer).any(|item| item.name == "Peach Cobbler"));
+    assert!(menu.available_in(Season::Winter).all(|item| item.name != "Peach Cobbler"));
+    assert!(menu
+        .order(&[Appetizer::Salad.menu_name(), "Lemonade"], Season::Spring)
+        .is_ok());
+    assert!(matches!(
+        menu.order(&[Appetizer::Salad.menu_name()], Season::Winter),
+        Err(MenuError::OutOfSeason { season: Season::Winter, .. })
+    ));
+    assert!(matches!(
+        menu.order(&["Pizza"], Season::Summer),
+        Err(MenuError::UnknownItem(name)) if name == "Pizza"
+    ));
+}
+
+#[test]
+fn breakfast_fruit_follows_the_season() {
+    assert_eq!(Breakfast::summer("Rye").seasonal_fruit(), "peaches");
+    assert_eq!(Breakfast::for_season("Rye", Season::Winter).seasonal_fruit(), "oranges");
+}
+
+#[test]
+fn malformed_lines_are_reported() {
+    let error = Menu::parse("# header\ndrink | Tea | 200 | all\n").unwrap_err();
+    assert!(matches!(error, MenuError::Parse { line: 2, .. }));
+
+    let error = Menu::parse("drink | Tea | cheap | all |").unwrap_err();
+    assert_eq!(error.to_string(), "menu line 1: invalid price 'cheap'");
+}
@@ -0,0 +1,87 @@
+use restaurant::back_of_house;
+use restaurant::billing::{Bill, BillingError, Cash, Money};
+use restaurant::menu::Menu;
+use restaurant::{deliver_order, eat_at_restaurant, Inventory, Season};
+use restaurant::order::{OrderError, OrderStatus};
+use restaurant::serving;
+
+#[test]
+fn eat_at_restaurant_goes_through_every_stage() {
+    let order = eat_at_restaurant().unwrap();
+
+    assert_eq!(order.status(), OrderStatus::Paid);
+    assert_eq!(
+        order.history(),
+        [
+            OrderStatus::Placed,
+            OrderStatus::Cooking,
+            OrderStatus::Ready,
+            OrderStatus::Served,
+            OrderStatus::Paid,
+        ]
+    );
+}
+
+#[test]
+fn stages_cannot_be_skipped() {
+    let mut order = serving::take_order(7, 3, vec![String::from("Salad")]);
+    let bill = Bill::for_order(&order, &Menu::default(), 0).unwrap();
+    let mut cash = Cash::new(Money::from_cents(1_000));
+
+    let error = serving::take_payment(&mut order, &bill, &mut [(&mut cash, bill.total())]);
+    assert!(matches!(
+        error,
+        Err(BillingError::Order(OrderError {
+            order: 7,
+            from: OrderStatus::Placed,
+            to: OrderStatus::Paid,
+        }))
+    ));
+    assert_eq!(cash.tendered, Money::from_cents(1_000));
+    assert!(serving::serve_order(&mut order).is_err());
+    assert_eq!(order.status(), OrderStatus::Placed);
+}
+
+#[test]
+fn served_order_can_be_remade() {
+    let mut inventory = Inventory::stocked(Season::Summer, 2);
+    let mut menu = Menu::default();
+    let salad = || vec![String::from("Salad")];
+
+    let mut order = serving::take_order(2, 4, vec![String::from("Soup")]);
+    back_of_house::cook_order(&mut order, &mut inventory, &mut menu).unwrap();
+
+    // Still in the kitchen, so there is nothing to fix yet
+    let error = back_of_house::fix_incorrect_order(&mut order, salad(), &mut inventory, &mut menu);
+    assert!(error.is_err());
+
+    deliver_order(&mut order).unwrap();
+    serving::serve_order(&mut order).unwrap();
+    back_of_house::fix_incorrect_order(&mut order, salad(), &mut inventory, &mut menu).unwrap();
+    assert_eq!(inventory.ledger().last().unwrap().reason, "remake of order #2");
+    assert_eq!(order.status(), OrderStatus::Cooking);
+    deliver_order(&mut order).unwrap();
+
+    assert_eq!(order.items, [String::from("Salad")]);
+    assert_eq!(order.status(), OrderStatus::Ready);
+    serving::serve_order(&mut order).unwrap();
+    let bill = Bill::for_order(&order, &Menu::default(), 0).unwrap();
+    let mut cash = Cash::new(Money::from_cents(1_000));
+    serving::take_payment(&mut order, &bill, &mut [(&mut cash, bill.total())]).unwrap();
+
+    // Another order's bill is refused before anyone is charged
+    let mut other = serving::take_order(3, 4, vec![String::from("Soup")]);
+    back_of_house::cook_order(&mut other, &mut inventory, &mut menu).unwrap();
+    deliver_order(&mut other).unwrap();
+    serving::serve_order(&mut other).unwrap();
+    let mut cash = Cash::new(Money::from_cents(1_000));
+    let error = serving::take_payment(&mut other, &bill, &mut [(&mut cash, bill.total())]);
+    assert!(matches!(error, Err(BillingError::WrongBill { order: 3, bill: 2 })));
+    assert_eq!(cash.tendered, Money::from_cents(1_000));
+    assert_eq!(other.status(), OrderStatus::Served);
+
+    // Once paid, an order is final
+    let error =
+        back_of_house::fix_incorrect_order(&mut order, Vec::new(), &mut inventory, &mut menu);
+    assert!(error.is_err());
+}
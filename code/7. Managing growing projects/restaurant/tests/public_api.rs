@@ -0,0 +1,37 @@
+// Everything a caller needs is reachable from the crate root, without knowing the module layout.
+use restaurant::{
+    hosting, kitchen, serving, Bill, Host, Inventory, Kitchen, Menu, Money, Order, OrderStatus,
+    Party, SeatingStrategy, Season, Shift, Table, Tip,
+};
+
+#[test]
+fn root_re_exports_match_their_modules() {
+    let _: hosting::Host = Host::new(vec![Table::new(1, 2)], SeatingStrategy::Fifo);
+    let _: kitchen::Kitchen = Kitchen::standard();
+    let _: restaurant::menu::Menu = Menu::default();
+}
+
+#[test]
+fn a_meal_can_be_driven_from_the_root_api() {
+    let mut host = Host::new(vec![Table::new(1, 4)], SeatingStrategy::Fifo);
+    host.add_to_waitlist(Party::new("Crab", 3, 0)).unwrap();
+    let table = host.seat_at_table(0)[0].table;
+
+    let menu = Menu::default();
+    let items = menu.order(&["Soup", "Salad", "Lemonade"], Season::Spring).unwrap();
+    let names = items.iter().map(|item| item.name.clone()).collect();
+    let mut order: Order = serving::take_order(1, table, names);
+
+    let mut kitchen = Kitchen::standard();
+    kitchen.cook_order(&mut order, 0).unwrap();
+    assert_eq!(kitchen.run().tickets.len(), 1);
+    restaurant::deliver_order(&mut order).unwrap();
+    serving::serve_order(&mut order).unwrap();
+
+    let bill = Bill::for_order(&order, &menu, 0).unwrap().with_tip(Tip::None);
+    assert_eq!(bill.total(), Money::from_cents(650 + 700 + 350));
+    assert_eq!(order.status(), OrderStatus::Served);
+
+    let shift = Shift::open(1, Inventory::new(Season::Spring), menu);
+    assert!(shift.close().unwrap().served.is_empty());
+}
@@ -0,0 +1,194 @@
+use std::env;
+use std::fs;
+
+use restaurant::reservations::{
+    Date, ReservationBook, ReservationError, ReservationStatus, TimeSlot,
+};
+use restaurant::{Host, Party, SeatingStrategy, Table};
+
+const FRIDAY: Date = Date {
+    year: 2026,
+    month: 10,
+    day: 23,
+};
+
+fn book() -> ReservationBook {
+    // Ten seats, each reservation holding its table for two hours
+    ReservationBook::new(10, 120)
+}
+
+#[test]
+fn overlapping_reservations_share_the_capacity() {
+    let mut book = book();
+    book.book("Ferris", 6, TimeSlot::at(FRIDAY, 19, 0)).unwrap();
+
+    assert_eq!(book.available(TimeSlot::at(FRIDAY, 20, 30)), 4);
+    assert_eq!(book.available(TimeSlot::at(FRIDAY, 21, 0)), 10);
+    assert_eq!(book.available(TimeSlot::at(FRIDAY, 17, 30)), 4);
+    assert!(matches!(
+        book.book("Corro", 5, TimeSlot::at(FRIDAY, 18, 0)),
+        Err(ReservationError::Full { available: 4, .. })
+    ));
+
+    // The next day is a different story
+    let saturday = Date { day: 24, ..FRIDAY };
+    book.book("Corro", 5, TimeSlot::at(saturday, 19, 0)).unwrap();
+}
+
+#[test]
+fn late_reservations_hold_their_seats_past_midnight() {
+    let mut book = book();
+    let new_years_eve = Date {
+        year: 2026,
+        month: 12,
+        day: 31,
+    };
+    let new_year = Date {
+        year: 2027,
+        month: 1,
+        day: 1,
+    };
+    book.book("Ferris", 6, TimeSlot::at(new_years_eve, 23, 0)).unwrap();
+
+    assert_eq!(book.available(TimeSlot::at(new_year, 0, 30)), 4);
+    assert_eq!(book.available(TimeSlot::at(new_year, 1, 0)), 10);
+    // A booking before midnight sees the ones starting after it
+    book.book("Corro", 4, TimeSlot::at(new_year, 0, 15)).unwrap();
+    assert_eq!(book.available(TimeSlot::at(new_years_eve, 22, 30)), 0);
+
+    // Long slots and late grace periods don't overflow
+    let mut long = ReservationBook::new(10, u16::MAX);
+    long.book("Ferris", 2, TimeSlot::at(FRIDAY, 23, 59)).unwrap();
+    assert_eq!(long.available(TimeSlot::at(FRIDAY, 23, 59)), 8);
+    assert!(long.mark_late_no_shows(TimeSlot::at(FRIDAY, 23, 59), u16::MAX).is_empty());
+}
+
+#[test]
+fn late_bookings_become_no_shows_after_midnight() {
+    let mut book = book();
+    let late = book.book("Ferris", 2, TimeSlot::at(FRIDAY, 23, 50)).unwrap();
+    let saturday = Date { day: 24, ..FRIDAY };
+
+    assert!(book.mark_late_no_shows(TimeSlot::at(saturday, 0, 3), 15).is_empty());
+    assert_eq!(book.mark_late_no_shows(TimeSlot::at(saturday, 0, 10), 15), [late]);
+    assert_eq!(book.get(late).unwrap().status, ReservationStatus::NoShow);
+}
+
+#[test]
+fn slots_are_times_of_day() {
+    assert_eq!(TimeSlot::at(FRIDAY, 23, 59).start, 23 * 60 + 59);
+    assert_eq!(TimeSlot::try_at(FRIDAY, 24, 0), None);
+    assert_eq!(TimeSlot::try_at(FRIDAY, 12, 60), None);
+}
+
+#[test]
+fn dates_must_exist_in_their_month() {
+    let date = |text: &str| text.parse::<Date>();
+
+    assert_eq!(date("2024-02-29"), Ok(Date { year: 2024, month: 2, day: 29 }));
+    assert!(date("2024-02-30").is_err());
+    assert!(date("2026-02-29").is_err());
+    assert!(date("2026-02-30").is_err());
+    assert!(date("2000-02-29").is_ok(), "divisible by 400");
+    assert!(date("1900-02-29").is_err(), "divisible by 100 but not 400");
+    assert!(date("2026-04-30").is_ok());
+    assert!(date("2026-04-31").is_err());
+    assert!(date("2026-12-31").is_ok());
+    assert!(date("2026-13-01").is_err());
+    assert!(date("2026-01-00").is_err());
+}
+
+#[test]
+#[should_panic(expected = "25:00 is not a time of day")]
+fn invalid_times_are_rejected() {
+    TimeSlot::at(FRIDAY, 25, 0);
+}
+
+#[test]
+fn cancellations_and_no_shows_free_their_seats() {
+    let mut book = book();
+    let slot = TimeSlot::at(FRIDAY, 19, 0);
+    let first = book.book("Ferris", 6, slot).unwrap();
+    let second = book.book("Corro", 4, TimeSlot::at(FRIDAY, 19, 15)).unwrap();
+    assert_eq!(book.available(slot), 0);
+
+    book.cancel(first).unwrap();
+    assert_eq!(book.available(slot), 6);
+    assert!(matches!(
+        book.cancel(first),
+        Err(ReservationError::NotBooked { status: ReservationStatus::Cancelled, .. })
+    ));
+
+    // Fifteen minutes of grace: at 19:25 Corro is still on time, at 19:35 they are not
+    assert!(book.mark_late_no_shows(TimeSlot::at(FRIDAY, 19, 25), 15).is_empty());
+    assert_eq!(book.mark_late_no_shows(TimeSlot::at(FRIDAY, 19, 35), 15), [second]);
+    assert_eq!(book.available(slot), 10);
+    assert!(matches!(book.mark_no_show(99), Err(ReservationError::UnknownReservation(99))));
+}
+
+#[test]
+fn checked_in_reservations_go_ahead_of_walk_ins() {
+    let mut book = book();
+    let id = book.book("Ferris", 2, TimeSlot::at(FRIDAY, 19, 0)).unwrap();
+    let mut host = Host::new(vec![Table::new(1, 2)], SeatingStrategy::Priority);
+
+    host.add_to_waitlist(Party::new("Walk-in", 2, 0)).unwrap();
+    book.check_in(id, &mut host, 5).unwrap();
+
+    assert_eq!(book.get(id).unwrap().status, ReservationStatus::Arrived);
+    assert_eq!(host.seat_at_table(5)[0].party.name, "Ferris");
+    assert!(book.check_in(id, &mut host, 6).is_err());
+}
+
+#[test]
+fn reservations_survive_a_save_and_load() {
+    let mut book = book();
+    book.book("Ferris", 2, TimeSlot::at(FRIDAY, 19, 0)).unwrap();
+    let cancelled = book.book("Corro", 4, TimeSlot::at(FRIDAY, 20, 45)).unwrap();
+    book.cancel(cancelled).unwrap();
+
+    let path = env::temp_dir().join(format!("reservations-{}.tsv", std::process::id()));
+    book.save(&path).unwrap();
+    let loaded = ReservationBook::load(&path, 10, 120).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, book);
+    assert!(matches!(
+        book.book("Tab\tby", 2, TimeSlot::at(FRIDAY, 12, 0)),
+        Err(ReservationError::InvalidName(_))
+    ));
+}
+
+#[test]
+fn reservations_export_as_icalendar() {
+    let mut book = book();
+    book.book("Ferris; Corro, and friends", 4, TimeSlot::at(FRIDAY, 19, 30)).unwrap();
+
+    let calendar = book.to_icalendar();
+    let lines: Vec<&str> = calendar.split("\r\n").collect();
+
+    assert_eq!(lines[0], "BEGIN:VCALENDAR");
+    assert!(lines.contains(&"DTSTART:20261023T193000"));
+    assert!(lines.contains(&"DURATION:PT120M"));
+    assert!(lines.contains(&r"SUMMARY:Table for 4 - Ferris\; Corro\, and friends"));
+    assert!(lines.contains(&"STATUS:CONFIRMED"));
+    assert_eq!(lines[lines.len() - 2], "END:VCALENDAR");
+
+    // Stamped with the time of the export, in UTC
+    let stamp = lines.iter().find_map(|line| line.strip_prefix("DTSTAMP:")).unwrap();
+    assert_eq!(stamp.len(), 16);
+    assert!(stamp.starts_with("20") && stamp.ends_with('Z') && stamp.as_bytes()[8] == b'T');
+}
+
+#[test]
+fn long_icalendar_lines_are_folded() {
+    let mut book = book();
+    let name = "Ferris the crab and every single one of the Rustaceans from the café";
+    book.book(name, 4, TimeSlot::at(FRIDAY, 19, 30)).unwrap();
+
+    let calendar = book.to_icalendar();
+    assert!(calendar.split("\r\n").all(|line| line.len() <= 75));
+
+    let unfolded = calendar.replace("\r\n ", "");
+    assert!(unfolded.contains(&format!("SUMMARY:Table for 4 - {name}\r\n")));
+}
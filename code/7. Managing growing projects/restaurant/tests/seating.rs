@@ -0,0 +1,93 @@
+use restaurant::hosting::{
+    HostingError, Host, Party, SeatingStrategy, Table,
+};
+
+fn dining_room(strategy: SeatingStrategy) -> Host {
+    Host::new(
+        vec![Table::new(1, 2), Table::new(2, 4), Table::new(3, 6)],
+        strategy,
+    )
+}
+
+fn seated(host: &mut Host, now: u32) -> Vec<(String, u32)> {
+    host.seat_at_table(now)
+        .into_iter()
+        .map(|seating| (seating.party.name, seating.table))
+        .collect()
+}
+
+#[test]
+fn fifo_does_not_let_anyone_jump_the_line() {
+    let mut host = dining_room(SeatingStrategy::Fifo);
+    host.add_to_waitlist(Party::new("Big", 6, 0)).unwrap();
+    host.add_to_waitlist(Party::new("Bigger", 5, 1)).unwrap();
+    host.add_to_waitlist(Party::new("Couple", 2, 2)).unwrap();
+
+    // The six-top goes to Big, and Bigger blocks Couple even though table 1 is free
+    assert_eq!(seated(&mut host, 5), [(String::from("Big"), 3)]);
+    assert_eq!(host.waitlist().count(), 2);
+
+    host.free_table(3).unwrap();
+    assert_eq!(
+        seated(&mut host, 50),
+        [(String::from("Bigger"), 3), (String::from("Couple"), 1)]
+    );
+}
+
+#[test]
+fn best_fit_wastes_the_fewest_seats() {
+    let mut host = dining_room(SeatingStrategy::BestFit);
+    host.add_to_waitlist(Party::new("Trio", 3, 0)).unwrap();
+    host.add_to_waitlist(Party::new("Six", 6, 1)).unwrap();
+    host.add_to_waitlist(Party::new("Duo", 2, 2)).unwrap();
+
+    assert_eq!(
+        seated(&mut host, 5),
+        [
+            (String::from("Six"), 3),
+            (String::from("Duo"), 1),
+            (String::from("Trio"), 2),
+        ]
+    );
+}
+
+#[test]
+fn priority_parties_go_first_and_small_parties_fill_gaps() {
+    let mut host = Host::new(vec![Table::new(1, 2), Table::new(2, 4)], SeatingStrategy::Priority);
+    host.add_to_waitlist(Party::new("Walk-in", 4, 0)).unwrap();
+    host.add_to_waitlist(Party::new("VIP", 4, 1).with_priority(1)).unwrap();
+    host.add_to_waitlist(Party::new("Solo", 1, 2)).unwrap();
+
+    assert_eq!(
+        seated(&mut host, 5),
+        [(String::from("VIP"), 2), (String::from("Solo"), 1)]
+    );
+    assert_eq!(host.waitlist().next().unwrap().name, "Walk-in");
+}
+
+#[test]
+fn waits_are_estimated_from_table_turnover() {
+    let mut host = Host::new(vec![Table::new(1, 4)], SeatingStrategy::Fifo);
+    host.dining_minutes = 30;
+    host.add_to_waitlist(Party::new("First", 4, 0)).unwrap();
+    host.seat_at_table(0);
+    host.add_to_waitlist(Party::new("Second", 2, 10)).unwrap();
+    host.add_to_waitlist(Party::new("Third", 3, 12)).unwrap();
+
+    assert_eq!(host.estimate_wait("Second", 15), Some(15));
+    assert_eq!(host.estimate_wait("Third", 15), Some(45));
+    assert_eq!(host.estimate_wait("Nobody", 15), None);
+}
+
+#[test]
+fn impossible_parties_are_refused() {
+    let mut host = dining_room(SeatingStrategy::Fifo);
+
+    assert_eq!(
+        host.add_to_waitlist(Party::new("Crowd", 7, 0)),
+        Err(HostingError::NoTableLargeEnough { size: 7 })
+    );
+    assert_eq!(host.add_to_waitlist(Party::new("Ghost", 0, 0)), Err(HostingError::EmptyParty));
+    assert_eq!(host.free_table(1), Err(HostingError::TableAlreadyFree(1)));
+    assert_eq!(host.free_table(9), Err(HostingError::UnknownTable(9)));
+}
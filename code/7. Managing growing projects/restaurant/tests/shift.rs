@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+use std::thread;
+
+use restaurant::order::OrderStatus;
+use restaurant::shift::{Shift, ShiftError};
+use restaurant::{Inventory, InventoryError, KitchenError, Menu, Season};
+
+fn open(cooks: usize) -> Shift {
+    Shift::open(cooks, Inventory::stocked(Season::Summer, 2_000), Menu::default())
+}
+
+#[test]
+fn every_order_is_served_exactly_once() {
+    const WAITERS: u32 = 8;
+    const ORDERS_PER_WAITER: u32 = 250;
+
+    let shift = open(4);
+
+    let waiters: Vec<_> = (0..WAITERS)
+        .map(|table| {
+            let waiter = shift.waiter();
+            thread::spawn(move || {
+                (0..ORDERS_PER_WAITER)
+                    .map(|_| waiter.take_order(table, vec![String::from("Soup")]).unwrap())
+                    .collect::<Vec<u32>>()
+            })
+        })
+        .collect();
+
+    let taken: Vec<u32> = waiters
+        .into_iter()
+        .flat_map(|waiter| waiter.join().unwrap())
+        .collect();
+    let report = shift.close().unwrap();
+
+    let served: Vec<u32> = report.served.iter().map(|order| order.id()).collect();
+    let unique: HashSet<u32> = served.iter().copied().collect();
+
+    assert_eq!(taken.len(), (WAITERS * ORDERS_PER_WAITER) as usize);
+    assert_eq!(served.len(), taken.len(), "no order is lost");
+    assert_eq!(unique.len(), served.len(), "no order is served twice");
+    assert_eq!(unique, taken.into_iter().collect());
+    assert_eq!(report.tickets_per_cook.iter().sum::<usize>(), served.len());
+    let lifecycle = [
+        OrderStatus::Placed,
+        OrderStatus::Cooking,
+        OrderStatus::Ready,
+        OrderStatus::Served,
+    ];
+    assert!(report.served.iter().all(|order| order.history() == lifecycle));
+}
+
+#[test]
+fn closing_an_idle_shift_stops_every_worker() {
+    let report = open(3).close().unwrap();
+
+    assert!(report.served.is_empty());
+    assert_eq!(report.tickets_per_cook, [0, 0, 0]);
+}
+
+#[test]
+fn tickets_bounce_when_nobody_is_cooking() {
+    let shift = open(0);
+    let waiter = shift.waiter();
+
+    assert_eq!(
+        waiter.take_order(1, vec![String::from("Soup")]),
+        Err(ShiftError::KitchenClosed)
+    );
+    drop(waiter);
+    assert!(shift.close().unwrap().served.is_empty());
+}
+
+#[test]
+fn orders_are_turned_away_once_the_soup_runs_out() {
+    let shift = Shift::open(2, Inventory::stocked(Season::Summer, 3), Menu::default());
+    let waiter = shift.waiter();
+    for table in 0..5 {
+        waiter.take_order(table, vec![String::from("Soup")]).unwrap();
+    }
+    drop(waiter);
+
+    let report = shift.close().unwrap();
+    assert_eq!(report.served.len(), 3);
+    assert_eq!(report.turned_away.len(), 2);
+    assert!(report.turned_away.iter().all(|(order, error)| {
+        order.status() == OrderStatus::Placed
+            && matches!(error, KitchenError::Inventory(InventoryError::OutOfStock { .. }))
+    }));
+    assert!(report.menu.is_sold_out("Soup"));
+    assert!(!report.menu.is_sold_out("Coffee"));
+    assert_eq!(report.inventory.stock("stock"), 0);
+}
@@ -1 +0,0 @@
-pub mod hosting;
\ No newline at end of file
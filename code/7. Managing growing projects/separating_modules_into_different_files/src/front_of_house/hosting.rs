@@ -1 +0,0 @@
-pub fn add_to_waitlist() {}
\ No newline at end of file
@@ -1,19 +1,14 @@
 use std::collections::HashMap;
 
-pub fn main() {
-    let numbers = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
-    calculations(numbers);
-}
-
 /// Main function to perform calculations and display results
 ///
 /// # Arguments
 ///
-/// * 'vec' - A vector of integers to be analyzed.
+/// * 'vec' - A non-empty vector of integers to be analyzed.
 ///
 /// This function sorts the vector, calculates the median and mode, and
 /// displays the results.
-fn calculations(mut vec: Vec<i32>) {
+pub fn calculations(mut vec: Vec<i32>) {
     // Sort the vector in ascending order
     vec.sort();
 
@@ -36,23 +31,23 @@ fn calculations(mut vec: Vec<i32>) {
 /// # Returns
 ///
 /// * The median value of the vector.
-fn calculate_median(vec: &Vec<i32>) -> i32 {
+fn calculate_median(vec: &[i32]) -> i32 {
     // Calculate the middle index
     let mid_index = vec.len() / 2;
 
     // Calculate the median depending on the length of the vector
-    let median = match vec.len() % 2 {
+    match vec.len() % 2 {
         0 => {
-            // If the length is even, return the average of the two middle values
-            (vec[mid_index - 1] + vec[mid_index]) / 2
+            // If the length is even, return the average of the two middle values, adding them
+            // as i64 so that two large values don't overflow. The average of two i32 values
+            // always fits back into an i32.
+            ((i64::from(vec[mid_index - 1]) + i64::from(vec[mid_index])) / 2) as i32
         },
         _ => {
             // If the length is odd, return the middle value
             vec[mid_index]
         }
-    };
-
-    median
+    }
 }
 
 /// Function to calculate the mode
@@ -64,7 +59,7 @@ fn calculate_median(vec: &Vec<i32>) -> i32 {
 /// # Returns
 ///
 /// * The mode value of the vector, i.e., the value that occurs most frequently.
-fn calculate_mode(vec: &Vec<i32>) -> i32 {
+fn calculate_mode(vec: &[i32]) -> i32 {
     let mut mode_map = HashMap::new();
 
     // Count the occurrences of each number in the vector
@@ -99,4 +94,23 @@ fn calculate_mode(vec: &Vec<i32>) -> i32 {
 /// This function prints the median and mode to the console.
 fn display_results(median: i32, mode: i32) {
     println!("The median is: {median}, and the mode is: {mode}");
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_of_odd_and_even_lengths() {
+        assert_eq!(calculate_median(&[1, 3, 7]), 3);
+        assert_eq!(calculate_median(&[1, 3, 7, 9]), 5);
+        assert_eq!(calculate_median(&[-4, -1]), -2);
+    }
+
+    #[test]
+    fn median_of_extreme_values_does_not_overflow() {
+        assert_eq!(calculate_median(&[i32::MAX, i32::MAX]), i32::MAX);
+        assert_eq!(calculate_median(&[i32::MIN, i32::MIN]), i32::MIN);
+        assert_eq!(calculate_median(&[i32::MIN, i32::MAX]), 0);
+    }
+}
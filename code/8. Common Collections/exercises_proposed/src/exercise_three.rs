@@ -1,94 +1,583 @@
 use std::collections::HashMap;
-use std::hash::Hash;
-use std::io::{self, Write};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-pub fn main() {
-    let mut company = HashMap::new();
+/// Maximum edit distance for a name to be considered a fuzzy match or a "did you mean" suggestion
+const MAX_EDIT_DISTANCE: usize = 2;
 
-    loop {
-        // Get the action from the user
-        let action = get_user_input("Choose an action: add, list, or quit: ");
+/// File the audit log is appended to
+const AUDIT_LOG_PATH: &str = "audit.log";
 
+/// A mutation of the company directory that can be applied and reverted
+#[derive(Debug, Clone)]
+enum Command {
+    AddEmployee { employee: String, department: String },
+}
+
+impl Command {
+    /// Applies the command to the company without printing anything, so the audit log can be
+    /// replayed quietly
+    ///
+    /// # Arguments
+    ///
+    /// * 'company' - A mutable reference to the HashMap representing the company
+    fn apply(&self, company: &mut HashMap<String, Vec<String>>) {
+        match self {
+            Command::AddEmployee { employee, department } => {
+                add_employee(company, employee, department);
+            },
+        }
+    }
+
+    /// Reverts the effects of a previous 'apply' on the company
+    ///
+    /// # Arguments
+    ///
+    /// * 'company' - A mutable reference to the HashMap representing the company
+    fn revert(&self, company: &mut HashMap<String, Vec<String>>) {
+        match self {
+            Command::AddEmployee { employee, department } => {
+                remove_employee(company, employee, department);
+            },
+        }
+    }
+
+    /// Returns a human-readable description of the command
+    fn describe(&self) -> String {
+        match self {
+            Command::AddEmployee { employee, department } => {
+                format!("add {} to {}", employee, department)
+            },
+        }
+    }
+}
+
+/// What happened to a command when it was recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AuditAction {
+    Do,
+    Undo,
+    Redo,
+}
+
+/// A single timestamped entry of the audit log
+#[derive(Debug, Clone)]
+struct AuditEntry {
+    /// Milliseconds since the Unix epoch
+    timestamp: u128,
+    action: AuditAction,
+    command: Command,
+}
+
+/// An append-only record of every command executed, undone or redone
+struct AuditLog {
+    entries: Vec<AuditEntry>,
+    file: Option<File>,
+}
+
+impl AuditLog {
+    /// Creates an audit log that mirrors its entries to the file at 'path'
+    ///
+    /// Entries already in the file, from earlier sessions, are loaded first; lines that can't be
+    /// read are skipped. If the file can't be opened the log is kept in memory only.
+    fn open(path: &str) -> AuditLog {
+        let mut entries = Vec::new();
+
+        if let Ok(existing) = File::open(path) {
+            for (number, line) in BufReader::new(existing).lines().enumerate() {
+                match line.ok().as_deref().and_then(parse_audit_line) {
+                    Some(entry) => entries.push(entry),
+                    None => println!("Skipping unreadable line {} of {}", number + 1, path),
+                }
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path);
+
+        if let Err(error) = &file {
+            println!("Couldn't open audit log {}: {}", path, error);
+        }
+
+        AuditLog {
+            entries,
+            file: file.ok(),
+        }
+    }
+
+    /// Appends an entry stamped with the current time
+    ///
+    /// # Arguments
+    ///
+    /// * 'action' - Whether the command was done, undone or redone
+    /// * 'command' - The command the action was applied to
+    fn record(&mut self, action: AuditAction, command: &Command) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or(0);
+        let entry = AuditEntry {
+            timestamp,
+            action,
+            command: command.clone(),
+        };
+
+        if let Some(file) = &mut self.file {
+            if let Err(error) = writeln!(file, "{}", format_audit_line(&entry)) {
+                println!("Couldn't write to the audit log: {}", error);
+            }
+        }
+
+        self.entries.push(entry);
+    }
+
+    /// Rebuilds the company as it was at the given time by replaying the log
+    ///
+    /// # Arguments
+    ///
+    /// * 'timestamp' - Milliseconds since the Unix epoch; entries after it are ignored
+    ///
+    /// # Returns
+    ///
+    /// * The reconstructed company
+    fn state_at(&self, timestamp: u128) -> HashMap<String, Vec<String>> {
+        let mut company = HashMap::new();
+
+        for entry in self.entries.iter().take_while(|entry| entry.timestamp <= timestamp) {
+            match entry.action {
+                AuditAction::Do | AuditAction::Redo => entry.command.apply(&mut company),
+                AuditAction::Undo => entry.command.revert(&mut company),
+            }
+        }
+
+        company
+    }
+}
+
+/// Undo and redo stacks of the commands executed on the company
+struct History {
+    undo_stack: Vec<Command>,
+    redo_stack: Vec<Command>,
+    log: AuditLog,
+}
+
+impl History {
+    /// Creates an empty history that records to the given audit log
+    fn new(log: AuditLog) -> History {
+        History {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            log,
+        }
+    }
+
+    /// Applies a new command, making it undoable and clearing anything that could be redone
+    fn execute(&mut self, company: &mut HashMap<String, Vec<String>>, command: Command) {
+        command.apply(company);
+        self.log.record(AuditAction::Do, &command);
+        self.undo_stack.push(command);
+        self.redo_stack.clear();
+    }
+
+    /// Reverts the most recent command, if any
+    fn undo(&mut self, company: &mut HashMap<String, Vec<String>>) {
+        match self.undo_stack.pop() {
+            Some(command) => {
+                command.revert(company);
+                self.log.record(AuditAction::Undo, &command);
+                println!("Undid: {}", command.describe());
+                self.redo_stack.push(command);
+            },
+            None => println!("Nothing to undo"),
+        }
+    }
+
+    /// Re-applies the most recently undone command, if any
+    fn redo(&mut self, company: &mut HashMap<String, Vec<String>>) {
+        match self.redo_stack.pop() {
+            Some(command) => {
+                command.apply(company);
+                self.log.record(AuditAction::Redo, &command);
+                println!("Redid: {}", command.describe());
+                self.undo_stack.push(command);
+            },
+            None => println!("Nothing to redo"),
+        }
+    }
+}
+
+/// Runs the interactive employee directory
+///
+/// # Arguments
+///
+/// * 'input' - Where the actions are read from, e.g. the terminal or a script file
+///
+/// The directory stops on 'quit' or when the input runs out.
+pub fn run(input: &mut impl BufRead) {
+    let log = AuditLog::open(AUDIT_LOG_PATH);
+    // Pick up where the previous session left off; its commands can't be undone any more
+    let mut company = log.state_at(u128::MAX);
+    if !log.entries.is_empty() {
+        println!("Restored {} audit log entries from {}", log.entries.len(), AUDIT_LOG_PATH);
+    }
+    let mut history = History::new(log);
+
+    // Get the action from the user
+    while let Some(action) = get_user_input(
+        input,
+        "Choose an action: add, list, search, undo, redo, log, at, or quit: ",
+    ) {
         match action.as_str() {
             "add" => {
                 // Get the employee's name and department from the user
-                let employee = get_user_input("Enter the employee's name: ");
-                let department = get_user_input("Enter the department name: ");
-                add_employee(&mut company, &employee, &department);
+                let Some(employee) = get_user_input(input, "Enter the employee's name: ") else {
+                    break;
+                };
+                let Some(department) = get_user_input(input, "Enter the department name: ") else {
+                    break;
+                };
+                println!("Added {} to {}", employee, department);
+                history.execute(&mut company, Command::AddEmployee { employee, department });
+            },
+            "undo" => history.undo(&mut company),
+            "redo" => history.redo(&mut company),
+            "log" => {
+                for entry in &history.log.entries {
+                    println!("{}", format_audit_entry(entry));
+                }
+            },
+            "at" => {
+                // Rebuild the directory at a point in time taken from the log
+                let Some(timestamp) = get_user_input(
+                    input,
+                    "Enter a timestamp (milliseconds since the Unix epoch): ",
+                ) else {
+                    break;
+                };
+                match timestamp.parse() {
+                    Ok(timestamp) => list_all_employees(&history.log.state_at(timestamp)),
+                    Err(_) => println!("Invalid timestamp: {}", timestamp),
+                }
             },
             "list" => {
                 // Get the department name from the user
                 // If 'all' is entered, list all employees in all departments
-                let department = get_user_input("Enter the department name (or 'all' for all departments): ");
+                let Some(department) = get_user_input(
+                    input,
+                    "Enter the department name (or 'all' for all departments): ",
+                ) else {
+                    break;
+                };
                 if department == "all" {
                     list_all_employees(&company);
                 } else {
                     list_employees_by_department(&company, &department);
                 }
             },
+            "search" => {
+                // Get the search term from the user and look it up in employees and departments
+                let Some(query) = get_user_input(input, "Enter a name to search for: ") else {
+                    break;
+                };
+                search_directory(&company, &query);
+            },
             "quit" => break,
             _ => println!("Invalid action. Please try again."),
         }
     }
 }
 
-/// Function to get user input from the terminal
+/// Function to get user input
 ///
 /// # Arguments
 ///
+/// * 'input' - Where the line is read from
 /// * 'prompt' - A string slice that holds the prompt message to be displayed to the user
 ///
 /// # Returns
 ///
-/// * A 'String' containing the user input
-fn get_user_input(prompt: &str) -> String {
+/// * A 'String' containing the user input, or 'None' once the input is exhausted
+fn get_user_input(input: &mut impl BufRead, prompt: &str) -> Option<String> {
     // Print the prompt message to the user
     print!("{}", prompt);
-    // Ensure the prompt is displayed before reading input
-    io::stdout().flush().unwrap();
     // Flush to ensure the prompt appears before input
+    io::stdout().flush().unwrap();
 
-    let mut input = String::new();
-    // Read the user's input from the terminal
-    io::stdin().read_line(&mut input).expect("Failed to read line");
+    let mut line = String::new();
+    // Read the user's input; zero bytes read means the end of the input was reached
+    let bytes_read = input.read_line(&mut line).expect("Failed to read line");
+    if bytes_read == 0 {
+        println!();
+        return None;
+    }
 
     // Trim any leading and trailing whitespace from the input
-    input.trim().to_string()
+    Some(line.trim().to_string())
 }
 
-/// Function to list employees in a specific department
+/// Function to add an employee to a department
 ///
 /// # Arguments
 ///
-/// * 'company' - A reference to the HashMap representing the company
+/// * 'company' - A mutable reference to the HashMap representing the company
+/// * 'employee' - A string slice that holds the employee's name
 /// * 'department' - A string slice that holds the department's name
 fn add_employee(company: &mut HashMap<String, Vec<String>>, employee: &str, department: &str) {
     // Get the vector of employees for the department, or insert a new vector if it doesn't exist
-    let employees = company.entry(department.to_string()).or_insert(Vec::new());
+    let employees = company.entry(department.to_string()).or_default();
     // Add the employee to the department
     employees.push(employee.to_string());
-    println!("Added {} to {}", employee, department);
 }
 
-/// Function to list employees in a specific department
+/// Function to remove an employee from a department
+///
+/// The department is removed as well once it has no employees left.
 ///
 /// # Arguments
 ///
+/// * 'company' - A mutable reference to the HashMap representing the company
+/// * 'employee' - A string slice that holds the employee's name
+/// * 'department' - A string slice that holds the department's name
+fn remove_employee(company: &mut HashMap<String, Vec<String>>, employee: &str, department: &str) {
+    if let Some(employees) = company.get_mut(department) {
+        // Remove the most recently added employee with that name
+        if let Some(index) = employees.iter().rposition(|name| name == employee) {
+            employees.remove(index);
+        }
+
+        if employees.is_empty() {
+            company.remove(department);
+        }
+    }
+}
+
+/// Function to format an audit log entry as a single tab-separated line
+///
+/// # Arguments
+///
+/// * 'entry' - A reference to the entry to be formatted
+///
+/// # Returns
+///
+/// * A 'String' with the timestamp, the action and the command
+fn format_audit_entry(entry: &AuditEntry) -> String {
+    let action = match entry.action {
+        AuditAction::Do => "do",
+        AuditAction::Undo => "undo",
+        AuditAction::Redo => "redo",
+    };
+
+    format!("{}\t{}\t{}", entry.timestamp, action, entry.command.describe())
+}
+
+/// Function to format an audit log entry as a line of the audit log file
+///
+/// Unlike 'format_audit_entry', every field of the command gets its own column so the line
+/// can be read back with 'parse_audit_line'.
+///
+/// # Arguments
+///
+/// * 'entry' - A reference to the entry to be formatted
+fn format_audit_line(entry: &AuditEntry) -> String {
+    let action = match entry.action {
+        AuditAction::Do => "do",
+        AuditAction::Undo => "undo",
+        AuditAction::Redo => "redo",
+    };
+
+    match &entry.command {
+        Command::AddEmployee { employee, department } => {
+            format!("{}\t{}\tadd\t{}\t{}", entry.timestamp, action, employee, department)
+        },
+    }
+}
+
+/// Function to read back a line written by 'format_audit_line'
+///
+/// # Arguments
+///
+/// * 'line' - A string slice that holds one line of the audit log file
+///
+/// # Returns
+///
+/// * The entry, or 'None' if the line isn't a valid audit log entry
+fn parse_audit_line(line: &str) -> Option<AuditEntry> {
+    let mut fields = line.splitn(5, '\t');
+    let timestamp = fields.next()?.parse().ok()?;
+    let action = match fields.next()? {
+        "do" => AuditAction::Do,
+        "undo" => AuditAction::Undo,
+        "redo" => AuditAction::Redo,
+        _ => return None,
+    };
+    let command = match fields.next()? {
+        "add" => Command::AddEmployee {
+            employee: fields.next()?.to_string(),
+            department: fields.next()?.to_string(),
+        },
+        _ => return None,
+    };
+
+    Some(AuditEntry { timestamp, action, command })
+}
+
+/// Function to list employees in a specific department
+///
+/// The lookup is case-insensitive. If no department matches, similar department names are
+/// suggested.
+///
+/// # Arguments
 ///
 /// * 'company' - A reference to the HashMap representing the company
 /// * 'department' - A string slice that holds the department's name
 fn list_employees_by_department(company: &HashMap<String, Vec<String>>, department: &str) {
-    match company.get(department) {
-        Some(employees) => {
+    match find_department(company, department) {
+        Some((name, employees)) => {
             // Clone and sort the list of employees
             let mut sorted_employees = employees.clone();
             sorted_employees.sort();
-            println!("Employees in {}: {:?}", department, sorted_employees);
+            println!("Employees in {}: {:?}", name, sorted_employees);
+        },
+        None => {
+            println!("No department named {}", department);
+
+            let suggestions = suggest_departments(company, department);
+            if !suggestions.is_empty() {
+                println!("Did you mean: {}?", suggestions.join(", "));
+            }
         },
-        None => println!("No department named {}", department),
     }
 }
 
+/// Function to find a department by name, ignoring case
+///
+/// # Arguments
+///
+/// * 'company' - A reference to the HashMap representing the company
+/// * 'department' - A string slice that holds the department's name
+///
+/// # Returns
+///
+/// * The stored department name and its employees, or 'None' if there is no such department
+fn find_department<'a>(
+    company: &'a HashMap<String, Vec<String>>,
+    department: &str,
+) -> Option<(&'a String, &'a Vec<String>)> {
+    // An exact match always wins over a case-insensitive one
+    company.get_key_value(department).or_else(|| {
+        company
+            .iter()
+            .find(|(name, _)| name.to_lowercase() == department.to_lowercase())
+    })
+}
+
+/// Function to suggest department names close to a misspelled one
+///
+/// # Arguments
+///
+/// * 'company' - A reference to the HashMap representing the company
+/// * 'department' - A string slice that holds the misspelled department name
+///
+/// # Returns
+///
+/// * The department names within 'MAX_EDIT_DISTANCE', closest first
+fn suggest_departments(company: &HashMap<String, Vec<String>>, department: &str) -> Vec<String> {
+    let mut suggestions: Vec<(usize, &String)> = company
+        .keys()
+        .map(|name| (edit_distance(name, department), name))
+        .filter(|&(distance, _)| distance <= MAX_EDIT_DISTANCE)
+        .collect();
+    // Sort by distance, then alphabetically so the output is stable
+    suggestions.sort();
+
+    suggestions.into_iter().map(|(_, name)| name.clone()).collect()
+}
+
+/// Function to search employees and departments by name
+///
+/// A name matches if it contains the query, ignoring case, or if it is within
+/// 'MAX_EDIT_DISTANCE' edits of the query.
+///
+/// # Arguments
+///
+/// * 'company' - A reference to the HashMap representing the company
+/// * 'query' - A string slice that holds the text to search for
+fn search_directory(company: &HashMap<String, Vec<String>>, query: &str) {
+    // Collect and sort the department names so the results are printed in a stable order
+    let mut departments: Vec<_> = company.keys().collect();
+    departments.sort();
+
+    let mut found = false;
+
+    for department in departments {
+        if is_fuzzy_match(department, query) {
+            println!("Department: {}", department);
+            found = true;
+        }
+
+        let mut employees = company[department].clone();
+        employees.sort();
+
+        for employee in employees.iter().filter(|employee| is_fuzzy_match(employee, query)) {
+            println!("Employee: {} ({})", employee, department);
+            found = true;
+        }
+    }
+
+    if !found {
+        println!("No matches for {}", query);
+    }
+}
+
+/// Function to check if a name matches a search query
+///
+/// # Arguments
+///
+/// * 'name' - A string slice that holds the name to be checked
+/// * 'query' - A string slice that holds the text to search for
+///
+/// # Returns
+///
+/// * 'true' if the name contains the query ignoring case, or is close to it, 'false' otherwise
+fn is_fuzzy_match(name: &str, query: &str) -> bool {
+    name.to_lowercase().contains(&query.to_lowercase())
+        || edit_distance(name, query) <= MAX_EDIT_DISTANCE
+}
+
+/// Function to calculate the Levenshtein distance between two strings, ignoring case
+///
+/// # Arguments
+///
+/// * 'a' - The first string slice
+/// * 'b' - The second string slice
+///
+/// # Returns
+///
+/// * The minimum number of single-character insertions, deletions and substitutions needed to
+///   turn one string into the other
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+
+    // Only the previous row of the distance table is needed to compute the next one
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, &a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            let insertion = current[j] + 1;
+            let deletion = previous[j + 1] + 1;
+            current.push(substitution.min(insertion).min(deletion));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
 /// Function to list all employees in the company, sorted by department
 ///
 /// # Arguments
@@ -103,4 +592,146 @@ fn list_all_employees(company: &HashMap<String, Vec<String>>) {
     for department in departments {
         list_employees_by_department(company, department);
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(employee: &str, department: &str) -> Command {
+        Command::AddEmployee {
+            employee: employee.to_string(),
+            department: department.to_string(),
+        }
+    }
+
+    fn entry(timestamp: u128, action: AuditAction, command: Command) -> AuditEntry {
+        AuditEntry { timestamp, action, command }
+    }
+
+    fn in_memory_log() -> AuditLog {
+        AuditLog { entries: Vec::new(), file: None }
+    }
+
+    fn company() -> HashMap<String, Vec<String>> {
+        let mut company = HashMap::new();
+        add_employee(&mut company, "Sally", "Engineering");
+        add_employee(&mut company, "Amir", "Sales");
+        add_employee(&mut company, "Sal", "Marketing");
+        company
+    }
+
+    #[test]
+    fn edit_distance_counts_single_character_edits_ignoring_case() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("Sales", "sALES"), 0);
+        assert_eq!(edit_distance("Sales", "Slaes"), 2);
+        assert_eq!(edit_distance("Ana", "Anna"), 1);
+    }
+
+    #[test]
+    fn fuzzy_matches_substrings_and_near_misses() {
+        assert!(is_fuzzy_match("Engineering", "ENGINE"));
+        assert!(is_fuzzy_match("Sally", "ALL"));
+        assert!(is_fuzzy_match("Sally", "Sallie"));
+        assert!(!is_fuzzy_match("Sally", "Amir"));
+        assert!(!is_fuzzy_match("Marketing", "Mkting"));
+    }
+
+    #[test]
+    fn departments_are_found_ignoring_case() {
+        let company = company();
+
+        let (name, employees) = find_department(&company, "sales").unwrap();
+        assert_eq!(name, "Sales");
+        assert_eq!(employees, &["Amir"]);
+        assert!(find_department(&company, "Finance").is_none());
+
+        assert_eq!(suggest_departments(&company, "Sails"), ["Sales"]);
+        assert_eq!(suggest_departments(&company, "Enginering"), ["Engineering"]);
+        assert!(suggest_departments(&company, "Finance").is_empty());
+    }
+
+    #[test]
+    fn undo_and_redo_revert_and_reapply_commands() {
+        let mut company = HashMap::new();
+        let mut history = History::new(in_memory_log());
+
+        history.execute(&mut company, add("Sally", "Engineering"));
+        history.execute(&mut company, add("Amir", "Sales"));
+        history.undo(&mut company);
+        assert_eq!(company.get("Sales"), None);
+        assert_eq!(company["Engineering"], ["Sally"]);
+
+        history.redo(&mut company);
+        assert_eq!(company["Sales"], ["Amir"]);
+
+        // A new command clears what could be redone
+        history.undo(&mut company);
+        history.execute(&mut company, add("Bo", "Sales"));
+        history.redo(&mut company);
+        assert_eq!(company["Sales"], ["Bo"]);
+
+        let actions: Vec<AuditAction> = history.log.entries.iter().map(|e| e.action).collect();
+        assert_eq!(
+            actions,
+            [
+                AuditAction::Do,
+                AuditAction::Do,
+                AuditAction::Undo,
+                AuditAction::Redo,
+                AuditAction::Undo,
+                AuditAction::Do,
+            ]
+        );
+    }
+
+    #[test]
+    fn state_at_replays_the_log_up_to_a_time() {
+        let mut log = in_memory_log();
+        log.entries = vec![
+            entry(10, AuditAction::Do, add("Sally", "Engineering")),
+            entry(20, AuditAction::Do, add("Amir", "Sales")),
+            entry(30, AuditAction::Undo, add("Amir", "Sales")),
+            entry(40, AuditAction::Redo, add("Amir", "Sales")),
+        ];
+
+        assert!(log.state_at(5).is_empty());
+        assert_eq!(log.state_at(25)["Sales"], ["Amir"]);
+        assert_eq!(log.state_at(30).get("Sales"), None);
+        assert_eq!(log.state_at(30)["Engineering"], ["Sally"]);
+        assert_eq!(log.state_at(u128::MAX)["Sales"], ["Amir"]);
+    }
+
+    #[test]
+    fn audit_lines_round_trip() {
+        let original = entry(1_700_000_000_123, AuditAction::Undo, add("Ana Lee", "R and D"));
+        let line = format_audit_line(&original);
+        let parsed = parse_audit_line(&line).unwrap();
+
+        assert_eq!(parsed.timestamp, original.timestamp);
+        assert_eq!(parsed.action, original.action);
+        assert_eq!(parsed.command.describe(), "add Ana Lee to R and D");
+        assert!(parse_audit_line("not a timestamp\tdo\tadd\ta\tb").is_none());
+        assert!(parse_audit_line("1\tdo\tremove\ta\tb").is_none());
+        assert!(parse_audit_line("1\tdo\tadd\ta").is_none());
+    }
+
+    #[test]
+    fn open_loads_entries_from_earlier_sessions() {
+        let path = std::env::temp_dir().join(format!("audit-{}.log", std::process::id()));
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "10\tdo\tadd\tSally\tEngineering\ngarbage\n").unwrap();
+
+        let mut log = AuditLog::open(path);
+        assert_eq!(log.entries.len(), 1);
+        log.record(AuditAction::Do, &add("Amir", "Sales"));
+        drop(log);
+
+        let log = AuditLog::open(path);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(log.entries.len(), 2);
+        assert_eq!(log.state_at(u128::MAX)["Sales"], ["Amir"]);
+    }
+}
@@ -1,20 +1,29 @@
-pub fn main() {
-    // Test the conversion with the word "apple"
-    let word = "apple";
-    let pig_latin_word = convert_to_pig_latin(word.trim());
-    println!("{}", pig_latin_word);
-
-    // Test the conversion with the word "spanish"
-    let word = "spanish";
-    let pig_latin_word = convert_to_pig_latin(word.trim());
-    println!("{}", pig_latin_word);
+/// Function to convert every word of a text to Pig Latin.
+///
+/// # Arguments
+///
+/// * 'text' - A string slice that holds the text to be converted.
+///
+/// # Returns
+///
+/// * A 'String' with each line of the text converted word by word.
+pub fn convert_text(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(convert_to_pig_latin)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// Main function to convert a given word to Pig Latin.
 ///
 /// # Arguments
 ///
-/// * 'word' - A string slice that holds the word to be converted.
+/// * 'word' - A non-empty string slice that holds the word to be converted.
 ///
 /// # Returns
 ///
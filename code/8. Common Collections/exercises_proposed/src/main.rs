@@ -2,10 +2,197 @@ mod exercise_one;
 mod exercise_two;
 mod exercise_three;
 
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::process;
+
+const USAGE: &str = "\
+Usage: exercises_proposed <COMMAND> [ARGS...] [--file PATH]
+
+Commands:
+  stats [NUMBERS...]   Print the median and mode of a list of integers
+  piglatin [WORDS...]  Convert words to Pig Latin
+  directory            Run the interactive employee directory
+
+Inputs are taken from the arguments, from the file given with --file, or from stdin
+when neither is given, e.g.:
+  exercises_proposed stats 3 1 4 1 5 9 2 6 5 3 5
+  exercises_proposed piglatin apple spanish
+  echo \"first second\" | exercises_proposed piglatin
+  exercises_proposed directory --file actions.txt";
+
+/// Where a subcommand reads its input from
+enum Input {
+    Args(Vec<String>),
+    File(String),
+    Stdin,
+}
+
+impl Input {
+    /// Parses the arguments that follow the subcommand name
+    ///
+    /// # Arguments
+    ///
+    /// * 'args' - The arguments after the subcommand
+    ///
+    /// # Returns
+    ///
+    /// * The input source, or an error message if '--file' is misused
+    fn parse(args: &[String]) -> Result<Input, String> {
+        match args {
+            [] => Ok(Input::Stdin),
+            [flag, path] if flag == "--file" => Ok(Input::File(path.clone())),
+            [flag] if flag == "--file" => Err(String::from("--file needs a path")),
+            _ if args.iter().any(|arg| arg == "--file") => {
+                Err(String::from("--file can't be combined with other arguments"))
+            },
+            _ => Ok(Input::Args(args.to_vec())),
+        }
+    }
+
+    /// Reads the whole input as text, with arguments separated by spaces
+    fn read_to_string(self) -> Result<String, String> {
+        match self {
+            Input::Args(args) => Ok(args.join(" ")),
+            Input::File(path) => {
+                fs::read_to_string(&path).map_err(|error| format!("Couldn't read {}: {}", path, error))
+            },
+            Input::Stdin => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(|error| format!("Couldn't read stdin: {}", error))?;
+                Ok(text)
+            },
+        }
+    }
+}
+
 fn main() {
-    exercise_one::main();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    };
+
+    let result = Input::parse(rest).and_then(|input| match command.as_str() {
+        "stats" => stats(input),
+        "piglatin" => piglatin(input),
+        "directory" => directory(input),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        _ => Err(format!("Unknown command: {}\n\n{}", command, USAGE)),
+    });
+
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+/// Runs exercise one on whitespace- or comma-separated integers
+fn stats(input: Input) -> Result<(), String> {
+    let numbers = parse_numbers(&input.read_to_string()?)?;
+    exercise_one::calculations(numbers);
+    Ok(())
+}
+
+/// Parses whitespace- or comma-separated integers
+///
+/// # Arguments
+///
+/// * 'text' - The text to be parsed
+///
+/// # Returns
+///
+/// * The integers in order, or an error message if one isn't an integer or there are none
+fn parse_numbers(text: &str) -> Result<Vec<i32>, String> {
+    let numbers = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token
+                .parse::<i32>()
+                .map_err(|_| format!("Not an integer: {}", token))
+        })
+        .collect::<Result<Vec<i32>, String>>()?;
+
+    if numbers.is_empty() {
+        return Err(String::from("stats needs at least one number"));
+    }
+
+    Ok(numbers)
+}
+
+/// Runs exercise two on every word of the input
+fn piglatin(input: Input) -> Result<(), String> {
+    let text = input.read_to_string()?;
+    println!("{}", exercise_two::convert_text(&text));
+    Ok(())
+}
+
+/// Runs exercise three, reading the actions from a file or stdin
+fn directory(input: Input) -> Result<(), String> {
+    match input {
+        Input::Args(_) => Err(String::from(
+            "directory reads its actions from --file or stdin, not from arguments",
+        )),
+        Input::File(path) => {
+            let file = File::open(&path).map_err(|error| format!("Couldn't open {}: {}", path, error))?;
+            exercise_three::run(&mut BufReader::new(file));
+            Ok(())
+        },
+        Input::Stdin => {
+            exercise_three::run(&mut io::stdin().lock());
+            Ok(())
+        },
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn input_comes_from_arguments_a_file_or_stdin() {
+        assert!(matches!(Input::parse(&[]), Ok(Input::Stdin)));
+        assert!(matches!(
+            Input::parse(&args(&["--file", "actions.txt"])),
+            Ok(Input::File(path)) if path == "actions.txt"
+        ));
+        assert!(matches!(
+            Input::parse(&args(&["3", "1", "4"])),
+            Ok(Input::Args(values)) if values == ["3", "1", "4"]
+        ));
+    }
+
+    #[test]
+    fn file_flag_must_stand_alone_with_a_path() {
+        assert_eq!(Input::parse(&args(&["--file"])).err().unwrap(), "--file needs a path");
+        assert!(Input::parse(&args(&["1", "--file", "numbers.txt"])).is_err());
+        assert!(Input::parse(&args(&["--file", "a.txt", "b.txt"])).is_err());
+    }
 
-    exercise_two::main();
+    #[test]
+    fn arguments_are_read_as_one_line() {
+        let input = Input::parse(&args(&["apple", "spanish"])).unwrap();
+        assert_eq!(input.read_to_string().unwrap(), "apple spanish");
+    }
 
-    exercise_three::main();
+    #[test]
+    fn numbers_are_split_on_whitespace_and_commas() {
+        assert_eq!(parse_numbers("3 1,4\n1, -5").unwrap(), [3, 1, 4, 1, -5]);
+        assert_eq!(parse_numbers("3 x 4").err().unwrap(), "Not an integer: x");
+        assert!(parse_numbers("99999999999").is_err());
+        assert!(parse_numbers(" , \n").is_err());
+    }
 }